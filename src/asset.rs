@@ -1,5 +1,7 @@
-use bevy::asset::Asset;
+use bevy::asset::{Asset, Handle, UntypedHandle};
+use bevy::ecs::{entity::Entity, world::World};
 use bevy::reflect::TypePath;
+use bevy_flair::style::StyleSheet;
 
 use crate::ast::HtmlNode;
 
@@ -7,4 +9,50 @@ use crate::ast::HtmlNode;
 pub struct HtmlUiAsset {
 	pub source: String,
 	pub ast: Vec<HtmlNode>,
+	/// The `<template>` definitions removed from `ast`, kept for documents that include this one.
+	pub templates: Vec<HtmlNode>,
+	/// The `<link>` and `<style>` elements removed from `ast`, kept for documents that include this
+	/// one so that its styles apply there too.
+	pub styles: Vec<HtmlNode>,
+	/// Documents pulled in with `<include src="..." />`, already expanded into `ast`.
+	#[dependency]
+	pub includes: Vec<Handle<HtmlUiAsset>>,
+	/// Other assets referenced by attributes, such as the `src` of an `<img>`.
+	#[dependency]
+	pub dependencies: Vec<UntypedHandle>,
+	/// The CSS of the document's `<link>`ed stylesheets and `<style>` blocks, including those of the
+	/// documents it includes, loaded as the `style` sub-asset. When present, it
+	/// replaces the `css` of [`HtmlCssUiResource`](crate::HtmlCssUiResource) for this document.
+	#[dependency]
+	pub style_sheet: Option<Handle<StyleSheet>>,
+	/// Spawns the document under the given root in place of `ast`, for documents compiled with
+	/// `html_ui!`.
+	pub spawn: Option<fn(&mut World, Entity)>,
+}
+
+impl HtmlUiAsset {
+	/// Creates an asset from an already parsed document, such as one generated by `html_ui!`.
+	#[must_use]
+	pub fn new(ast: Vec<HtmlNode>) -> Self {
+		Self {
+			source: String::new(),
+			ast,
+			templates: Vec::new(),
+			styles: Vec::new(),
+			includes: Vec::new(),
+			dependencies: Vec::new(),
+			style_sheet: None,
+			spawn: None,
+		}
+	}
+
+	/// Creates an asset spawned by `spawn`, such as the function generated by `html_ui!`, rather
+	/// than from a parsed document.
+	#[must_use]
+	pub fn compiled(spawn: fn(&mut World, Entity)) -> Self {
+		Self {
+			spawn: Some(spawn),
+			..Self::new(Vec::new())
+		}
+	}
 }
@@ -1,14 +1,17 @@
+use std::str::FromStr;
+
 use bevy::prelude::Val;
 
 use crate::HtmlUiError;
 
-#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum HtmlNode {
 	Element(HtmlElement),
 	Text(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HtmlElement {
 	pub tag: HtmlTag,
 	pub name_id: Option<String>,
@@ -16,43 +19,76 @@ pub struct HtmlElement {
 	pub gap: Val,
 	pub autofocus: bool,
 	pub callback: Option<String>,
+	pub each: Option<HtmlEach>,
+	/// Every attribute not covered by a field above, in source order. Bare attributes have an empty value.
+	pub attributes: Vec<(String, String)>,
 	pub children: Vec<HtmlNode>,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+impl HtmlElement {
+	#[must_use]
+	pub fn attribute(&self, name: &str) -> Option<&str> {
+		self.attributes
+			.iter()
+			.find(|(key, _)| key == name)
+			.map(|(_, value)| value.as_str())
+	}
+}
+
+/// A parsed `for="item in path.to.list"` attribute, with its optional `key="item.id"`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HtmlEach {
+	pub binding: String,
+	pub collection: String,
+	pub key: Option<String>,
+}
+
+/// The name of an element. The tags the loader handles itself have their own variants, while every
+/// other tag is spawned by the handler registered under its name or replaced by the `<template>` of
+/// that name.
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum HtmlTag {
-	Ui,
-	VBox,
-	HBox,
-	Node,
-	Label,
-	Button,
-	Spacer,
+	Include,
+	Template,
+	Slot,
+	Style,
+	Link,
+	/// Any other tag, such as `vbox` or the name of a `<template>`.
+	Named(String),
 }
 
-impl HtmlTag {
-	pub fn from_str(s: &str) -> Result<Self, HtmlUiError> {
+impl FromStr for HtmlTag {
+	type Err = HtmlUiError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		match s {
-			"ui" => Ok(Self::Ui),
-			"vbox" => Ok(Self::VBox),
-			"hbox" => Ok(Self::HBox),
-			"node" => Ok(Self::Node),
-			"label" => Ok(Self::Label),
-			"button" => Ok(Self::Button),
-			"spacer" => Ok(Self::Spacer),
-			_ => Err(HtmlUiError::ParseError(format!("unknown tag `{s}`"))),
+			"include" => Ok(Self::Include),
+			"template" => Ok(Self::Template),
+			"slot" => Ok(Self::Slot),
+			"style" => Ok(Self::Style),
+			"link" => Ok(Self::Link),
+			_ if is_valid_tag_name(s) => Ok(Self::Named(s.to_owned())),
+			_ => Err(HtmlUiError::ParseError(format!("invalid tag name `{s}`"))),
 		}
 	}
+}
 
-	pub fn as_str(self) -> &'static str {
+impl HtmlTag {
+	#[must_use]
+	pub fn as_str(&self) -> &str {
 		match self {
-			Self::Ui => "ui",
-			Self::VBox => "vbox",
-			Self::HBox => "hbox",
-			Self::Node => "node",
-			Self::Label => "label",
-			Self::Button => "button",
-			Self::Spacer => "spacer",
+			Self::Include => "include",
+			Self::Template => "template",
+			Self::Slot => "slot",
+			Self::Style => "style",
+			Self::Link => "link",
+			Self::Named(name) => name,
 		}
 	}
 }
+
+fn is_valid_tag_name(s: &str) -> bool {
+	s.starts_with(|c: char| c.is_ascii_alphabetic())
+		&& s.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
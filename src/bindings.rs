@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use bevy::{log::warn, prelude::*};
+
+use crate::{
+	ast::{HtmlEach, HtmlElement, HtmlNode},
+	build::spawn_element,
+	data::{HtmlUiData, HtmlValue, has_interpolation, interpolate, split_path},
+};
+
+/// A loop variable introduced by a `for` attribute, visible to the entity and its descendants.
+#[derive(Component, Clone, PartialEq)]
+pub(crate) struct HtmlScope {
+	pub name: String,
+	pub value: HtmlValue,
+}
+
+/// Text containing `{{ path }}` interpolations, re-evaluated whenever the data changes.
+#[derive(Component)]
+pub(crate) struct HtmlTextBinding {
+	pub template: String,
+}
+
+/// Invisible marker spawned in place of an element with a `for` attribute.
+/// The list items are kept as the siblings directly after it.
+#[derive(Component)]
+pub(crate) struct HtmlForList {
+	pub element: HtmlElement,
+	pub each: HtmlEach,
+	pub items: Vec<(String, Entity)>,
+	/// The attribute values of the element and its descendants that contain interpolations, which
+	/// are only read when an item is spawned.
+	pub attributes: Vec<String>,
+}
+
+/// Resolves `path` against the `for` scopes of `entity` and its ancestors, then against [`HtmlUiData`].
+pub(crate) fn resolve_path(world: &World, entity: Entity, path: &str) -> Option<HtmlValue> {
+	let (head, rest) = split_path(path);
+
+	let mut current = Some(entity);
+	while let Some(e) = current {
+		let entity_ref = world.get_entity(e).ok()?;
+		if let Some(scope) = entity_ref.get::<HtmlScope>()
+			&& scope.name == head
+		{
+			return scope_value(scope, rest);
+		}
+		current = entity_ref.get::<ChildOf>().map(ChildOf::parent);
+	}
+
+	world.get_resource::<HtmlUiData>()?.get(path).cloned()
+}
+
+fn scope_value(scope: &HtmlScope, rest: Option<&str>) -> Option<HtmlValue> {
+	match rest {
+		Some(rest) => scope.value.get_path(rest).cloned(),
+		None => Some(scope.value.clone()),
+	}
+}
+
+pub(crate) fn interpolate_for(world: &World, entity: Entity, template: &str) -> String {
+	interpolate(template, |path| resolve_path(world, entity, path))
+}
+
+/// Like [`interpolate_for`], as if `scope` was the item of a list after `anchor`.
+fn interpolate_item(world: &World, anchor: Entity, scope: &HtmlScope, template: &str) -> String {
+	interpolate(template, |path| match split_path(path) {
+		(head, rest) if head == scope.name => scope_value(scope, rest),
+		_ => resolve_path(world, anchor, path),
+	})
+}
+
+/// Collects the attribute values of `element` and its descendants that contain interpolations.
+fn interpolated_attributes(element: &HtmlElement, values: &mut Vec<String>) {
+	values.extend(
+		element
+			.attributes
+			.iter()
+			.filter(|(_, value)| has_interpolation(value))
+			.map(|(_, value)| value.clone()),
+	);
+	for child in &element.children {
+		if let HtmlNode::Element(child) = child {
+			interpolated_attributes(child, values);
+		}
+	}
+}
+
+pub(crate) fn spawn_for_list(world: &mut World, parent: Entity, element: &HtmlElement) {
+	let Some(each) = element.each.clone() else {
+		return;
+	};
+
+	let mut attributes = Vec::new();
+	interpolated_attributes(element, &mut attributes);
+	let anchor = world
+		.spawn((
+			Node {
+				display: Display::None,
+				..default()
+			},
+			Pickable::IGNORE,
+			HtmlForList {
+				element: HtmlElement {
+					each: None,
+					..element.clone()
+				},
+				each,
+				items: Vec::new(),
+				attributes,
+			},
+		))
+		.id();
+
+	world.entity_mut(parent).add_child(anchor);
+
+	update_for_list(world, anchor);
+}
+
+/// Brings the items after `anchor` in line with its collection, keeping the entities of items
+/// whose key is unchanged and only spawning, despawning and reordering the difference.
+///
+/// Attributes are only interpolated when an item is spawned, so a kept item is respawned if the
+/// value of an interpolated attribute, such as `src="{{ item.icon }}"`, changes with it.
+fn update_for_list(world: &mut World, anchor: Entity) {
+	let Some(list) = world.get::<HtmlForList>(anchor) else {
+		return;
+	};
+	let element = list.element.clone();
+	let each = list.each.clone();
+	let attributes = list.attributes.clone();
+	let mut old_items: HashMap<String, Entity> = list.items.iter().cloned().collect();
+
+	let Some(parent) = world.get::<ChildOf>(anchor).map(ChildOf::parent) else {
+		return;
+	};
+
+	let values = match resolve_path(world, anchor, &each.collection) {
+		Some(HtmlValue::List(values)) => values,
+		Some(HtmlValue::Null) | None => Vec::new(),
+		Some(_) => {
+			warn!("`for` collection `{}` is not a list", each.collection);
+			Vec::new()
+		}
+	};
+
+	let mut items: Vec<(String, Entity)> = Vec::with_capacity(values.len());
+	for (index, value) in values.into_iter().enumerate() {
+		let key = each
+			.key
+			.as_deref()
+			.and_then(|key| item_key(&each.binding, key, &value))
+			.unwrap_or_else(|| format!("#{index}"));
+
+		if items.iter().any(|(k, _)| *k == key) {
+			warn!(
+				"duplicate key `{key}` in `for` list over `{}`",
+				each.collection
+			);
+			continue;
+		}
+
+		let scope = HtmlScope {
+			name: each.binding.clone(),
+			value,
+		};
+
+		let kept = old_items.remove(&key).filter(|entity| {
+			let Some(old) = world.get::<HtmlScope>(*entity) else {
+				return true;
+			};
+			let stale = *old != scope
+				&& attributes.iter().any(|template| {
+					interpolate_item(world, anchor, old, template)
+						!= interpolate_item(world, anchor, &scope, template)
+				});
+			if stale {
+				world.despawn(*entity);
+			}
+			!stale
+		});
+		let entity = if let Some(entity) = kept {
+			if let Some(mut existing) = world.get_mut::<HtmlScope>(entity) {
+				existing.set_if_neq(scope);
+			}
+			entity
+		} else {
+			spawn_element(world, parent, &element, Some(scope))
+		};
+
+		items.push((key, entity));
+	}
+
+	for entity in old_items.into_values() {
+		world.despawn(entity);
+	}
+
+	let ordered: Vec<Entity> = items.iter().map(|(_, entity)| *entity).collect();
+	if let Some(anchor_index) = world
+		.get::<Children>(parent)
+		.and_then(|children| children.iter().position(|child| child == anchor))
+	{
+		world
+			.entity_mut(parent)
+			.insert_children(anchor_index + 1, &ordered);
+	}
+
+	if let Some(mut list) = world.get_mut::<HtmlForList>(anchor) {
+		list.items = items;
+	}
+}
+
+fn item_key(binding: &str, key: &str, value: &HtmlValue) -> Option<String> {
+	let value = match split_path(key) {
+		(head, None) if head == binding => value,
+		(head, Some(rest)) if head == binding => value.get_path(rest)?,
+		_ => return None,
+	};
+	Some(value.to_string())
+}
+
+fn depth(world: &World, entity: Entity) -> usize {
+	let mut depth = 0;
+	let mut current = entity;
+	while let Some(child_of) = world.get::<ChildOf>(current) {
+		depth += 1;
+		current = child_of.parent();
+	}
+	depth
+}
+
+pub(crate) fn html_ui_update_bindings(world: &mut World) {
+	// Outer lists first, so inner lists see the updated scope of their item.
+	let mut q_lists = world.query_filtered::<Entity, With<HtmlForList>>();
+	let mut lists: Vec<(usize, Entity)> = q_lists
+		.iter(world)
+		.map(|entity| (depth(world, entity), entity))
+		.collect();
+	lists.sort_by_key(|(depth, _)| *depth);
+
+	for (_, anchor) in lists {
+		update_for_list(world, anchor);
+	}
+
+	let mut q_texts = world.query::<(Entity, &HtmlTextBinding)>();
+	let texts: Vec<(Entity, String)> = q_texts
+		.iter(world)
+		.map(|(entity, binding)| (entity, interpolate_for(world, entity, &binding.template)))
+		.collect();
+
+	for (entity, value) in texts {
+		if let Some(mut text) = world.get_mut::<Text>(entity) {
+			if text.0 != value {
+				text.0 = value;
+			}
+		} else if let Some(mut span) = world.get_mut::<TextSpan>(entity)
+			&& span.0 != value
+		{
+			span.0 = value;
+		}
+	}
+}
+
+#[cfg(test)]
+mod bindings_tests {
+	use bevy::prelude::*;
+
+	use super::html_ui_update_bindings;
+	use crate::{
+		build::spawn_node,
+		data::{HtmlUiData, HtmlValue},
+		parser::parse_htmlish,
+		tags::{HtmlTagRegistry, register_builtin_tags},
+	};
+
+	fn items(items: &[(&str, &str)]) -> HtmlValue {
+		HtmlValue::List(
+			items
+				.iter()
+				.map(|(id, icon)| HtmlValue::map([("id", *id), ("icon", *icon)]))
+				.collect(),
+		)
+	}
+
+	/// Spawns `source` with `list` as the `items` of the data, returning the root.
+	fn spawn_list(source: &str, list: HtmlValue) -> (App, Entity) {
+		let mut app = App::new();
+		app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+			.init_asset::<Image>()
+			.init_resource::<HtmlTagRegistry>()
+			.insert_resource(HtmlUiData::new().with("items", list));
+		register_builtin_tags(&mut app);
+
+		let world = app.world_mut();
+		let root = world.spawn(Node::default()).id();
+		for node in &parse_htmlish(&source.into()).unwrap() {
+			spawn_node(world, root, node);
+		}
+		(app, root)
+	}
+
+	fn set_items(app: &mut App, list: HtmlValue) {
+		let world = app.world_mut();
+		world.resource_mut::<HtmlUiData>().set("items", list);
+		html_ui_update_bindings(world);
+	}
+
+	/// The children of `root` after the list anchor, with their text.
+	fn item_texts(app: &App, root: Entity) -> Vec<(Entity, String)> {
+		let world = app.world();
+		world.get::<Children>(root).unwrap()[1..]
+			.iter()
+			.map(|item| (*item, world.get::<Text>(*item).unwrap().0.clone()))
+			.collect()
+	}
+
+	/// Keyed items keep their entities as they are reordered, inserted and removed.
+	#[test]
+	fn test_keyed_list() {
+		let (mut app, root) = spawn_list(
+			r#"<label for="item in items" key="item.id">{{ item.id }}</label>"#,
+			items(&[("a", ""), ("b", ""), ("c", "")]),
+		);
+		let before = item_texts(&app, root);
+		let [a, b, c] = [before[0].0, before[1].0, before[2].0];
+		assert_eq!(
+			before
+				.iter()
+				.map(|(_, text)| text.as_str())
+				.collect::<Vec<_>>(),
+			["a", "b", "c"]
+		);
+
+		set_items(&mut app, items(&[("c", ""), ("a", ""), ("b", "")]));
+		assert_eq!(
+			item_texts(&app, root),
+			[(c, "c".into()), (a, "a".into()), (b, "b".into())]
+		);
+
+		set_items(
+			&mut app,
+			items(&[("c", ""), ("d", ""), ("a", ""), ("b", "")]),
+		);
+		let after = item_texts(&app, root);
+		assert_eq!(after.len(), 4);
+		assert_eq!(after[0], (c, "c".into()));
+		assert_eq!(after[1].1, "d");
+		assert!(![a, b, c].contains(&after[1].0));
+		assert_eq!(after[2..], [(a, "a".into()), (b, "b".into())]);
+
+		set_items(&mut app, items(&[("b", ""), ("c", "")]));
+		assert_eq!(item_texts(&app, root), [(b, "b".into()), (c, "c".into())]);
+		assert!(app.world().get_entity(a).is_err());
+		assert!(app.world().get_entity(after[1].0).is_err());
+	}
+
+	/// An item is respawned when an attribute that interpolates it changes, and kept otherwise.
+	#[test]
+	fn test_keyed_list_attributes() {
+		let (mut app, root) = spawn_list(
+			r#"<hbox for="item in items" key="item.id"><img src="{{ item.icon }}" /><label>{{ item.id }}</label></hbox>"#,
+			items(&[("a", "a.png"), ("b", "b.png")]),
+		);
+		let before = app.world().get::<Children>(root).unwrap().to_vec();
+
+		set_items(&mut app, items(&[("a", "a.png"), ("b", "c.png")]));
+		let after = app.world().get::<Children>(root).unwrap().to_vec();
+		assert_eq!(after[1], before[1]);
+		assert_ne!(after[2], before[2]);
+		assert!(app.world().get_entity(before[2]).is_err());
+		let img = app.world().get::<Children>(after[2]).unwrap()[0];
+		let image = &app.world().get::<ImageNode>(img).unwrap().image;
+		assert_eq!(image.path().unwrap().to_string(), "c.png");
+	}
+}
@@ -1,13 +1,20 @@
-use bevy::{
-	input_focus::AutoFocus, prelude::*, ui::auto_directional_navigation::AutoDirectionalNavigation,
-};
+use bevy::{input_focus::AutoFocus, log::warn, prelude::*};
 use bevy_flair::prelude::*;
 
 use crate::{
 	asset::HtmlUiAsset,
 	ast::{HtmlElement, HtmlNode, HtmlTag},
+	bindings::{HtmlScope, HtmlTextBinding, interpolate_for, spawn_for_list},
+	data::has_interpolation,
+	direction::{apply_direction, insert_direction, insert_root_direction},
 	error::HtmlUiError,
+	grid::insert_grid_placement,
+	img::insert_background_image,
+	layout::insert_layout_attributes,
+	locale::{insert_translated_text, translate_attributes},
 	resources::HtmlCssUiResource,
+	span::{needs_text_run, text_run},
+	tags::HtmlTagRegistry,
 };
 
 #[derive(Component)]
@@ -26,9 +33,15 @@ pub(crate) fn spawn_html_ui(
 			return Err(HtmlUiError::ResourceNotFound);
 		};
 
+		// The document's own `<link>`ed stylesheets and `<style>` blocks take the place of the shared
+		// stylesheet.
+		let document_css = world
+			.resource::<Assets<HtmlUiAsset>>()
+			.get(id)
+			.and_then(|asset| asset.style_sheet.clone());
 		let mut style_sheet = NodeStyleSheet::Inherited;
-		if let Some(res_css) = &res.css {
-			style_sheet = NodeStyleSheet::new(res_css.clone());
+		if let Some(css) = document_css.or_else(|| res.css.clone()) {
+			style_sheet = NodeStyleSheet::new(css);
 		}
 
 		root_entity = world
@@ -44,140 +57,552 @@ pub(crate) fn spawn_html_ui(
 				style_sheet,
 			))
 			.id();
+		insert_root_direction(&mut world.entity_mut(root_entity));
 	}
 
 	let Some(asset) = world.resource::<Assets<HtmlUiAsset>>().get(id) else {
 		return Err(HtmlUiError::AssetNotFound);
 	};
 
-	let ast = asset.ast.clone();
-	for node in &ast {
-		spawn_node(world, root_entity, node);
+	if let Some(spawn) = asset.spawn {
+		spawn(world, root_entity);
+	} else {
+		let ast = asset.ast.clone();
+		for node in &ast {
+			spawn_node(world, root_entity, node);
+		}
 	}
 
 	Ok(root_entity)
 }
 
-#[allow(clippy::too_many_lines)]
-fn spawn_node(world: &mut World, parent: Entity, node: &HtmlNode) {
+#[doc(hidden)]
+pub fn spawn_node(world: &mut World, parent: Entity, node: &HtmlNode) {
 	match node {
-		HtmlNode::Text(text) => {
-			world.entity_mut(parent).insert(Text::new(text.clone()));
-		}
+		HtmlNode::Text(text) => spawn_text(world, parent, text),
 
-		HtmlNode::Element(HtmlElement {
-			tag,
-			name_id,
-			classes,
-			gap,
-			autofocus,
-			callback,
-			children,
-		}) => {
-			let mut entity: EntityWorldMut;
-			{
-				let Some(res) = world.get_resource::<HtmlCssUiResource>() else {
-					return;
-				};
-
-				let mut style_sheet = NodeStyleSheet::Inherited;
-				if let Some(res_css) = &res.css {
-					style_sheet = NodeStyleSheet::new(res_css.clone());
-				}
-
-				entity = world.spawn((Node::default(), style_sheet));
+		HtmlNode::Element(element) => {
+			if matches!(
+				element.tag,
+				HtmlTag::Include
+					| HtmlTag::Template
+					| HtmlTag::Slot | HtmlTag::Style
+					| HtmlTag::Link
+			) {
+				warn!("<{}> was not resolved by the loader", element.tag.as_str());
+			} else if element.each.is_some() {
+				spawn_for_list(world, parent, element);
+			} else {
+				spawn_element(world, parent, element, None);
 			}
+		}
+	}
+}
 
-			if let Some(name) = name_id {
-				entity.insert(Name::new(name.clone()));
-			}
+/// Sets the text of `parent`, or adds it as a `TextSpan` after the text and spans `parent` already
+/// has, as for the `to jump` of `<label>Press <b>A</b> to jump</label>`.
+///
+/// Next to node children, such as in a row of text and icons or after the box of a `<checkbox>`,
+/// the text goes into a run of text at the end of `parent` instead.
+#[doc(hidden)]
+pub fn spawn_text(world: &mut World, parent: Entity, text: &str) {
+	if needs_text_run(world, parent) {
+		let run = text_run(world, parent, Entity::PLACEHOLDER);
+		spawn_text(world, run, text);
+		return;
+	}
 
-			if *autofocus {
-				entity.insert(AutoFocus);
-			}
+	let binding = has_interpolation(text).then(|| HtmlTextBinding {
+		template: text.to_owned(),
+	});
+	let value = if binding.is_some() {
+		interpolate_for(world, parent, text)
+	} else {
+		text.to_owned()
+	};
 
-			if !classes.is_empty() {
-				entity.insert(ClassList::new(classes.join(" ").as_str()));
-			}
+	let parent_ref = world.entity(parent);
+	let is_span = parent_ref.contains::<TextSpan>();
+	let is_text = is_span || parent_ref.contains::<Text>();
+	let has_content = parent_ref
+		.get::<Text>()
+		.is_some_and(|text| !text.0.is_empty())
+		|| parent_ref
+			.get::<TextSpan>()
+			.is_some_and(|span| !span.0.is_empty())
+		|| parent_ref
+			.get::<Children>()
+			.is_some_and(|children| !children.is_empty());
+
+	let mut target = if is_text && has_content {
+		let mut span = world.spawn((TextSpan(value), NodeStyleSheet::Inherited));
+		span.insert(ChildOf(parent));
+		span
+	} else if is_span {
+		let mut span = world.entity_mut(parent);
+		span.insert(TextSpan(value));
+		span
+	} else {
+		let mut text = world.entity_mut(parent);
+		text.insert(Text::new(value));
+		text
+	};
+	if let Some(binding) = binding {
+		target.insert(binding);
+	}
+}
+
+pub(crate) fn spawn_element(
+	world: &mut World,
+	parent: Entity,
+	element: &HtmlElement,
+	scope: Option<HtmlScope>,
+) -> Entity {
+	let (entity_id, children) = begin_scoped_element(world, parent, element, scope);
+	for child in &element.children {
+		if children.spawns(child) {
+			spawn_node(world, entity_id, child);
+		}
+	}
+	end_element(world, entity_id, element);
+	entity_id
+}
+
+/// The children of an element that are spawned after [`begin_element`], since a tag may read them
+/// as data instead, and translated text replaces the text children.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct HtmlSpawnChildren {
+	text: bool,
+	elements: bool,
+}
+
+impl HtmlSpawnChildren {
+	#[must_use]
+	pub fn text(self) -> bool {
+		self.text
+	}
+
+	#[must_use]
+	pub fn elements(self) -> bool {
+		self.elements
+	}
+
+	fn spawns(self, node: &HtmlNode) -> bool {
+		match node {
+			HtmlNode::Text(_) => self.text,
+			HtmlNode::Element(_) => self.elements,
+		}
+	}
+}
+
+/// Spawns `element` under `parent` without its children, which are spawned next as far as the
+/// returned [`HtmlSpawnChildren`] allows, followed by [`end_element`].
+///
+/// Split up so that the code generated by [`html_ui!`](crate::html_ui) can spawn the children
+/// itself.
+#[doc(hidden)]
+pub fn begin_element(
+	world: &mut World,
+	parent: Entity,
+	element: &HtmlElement,
+) -> (Entity, HtmlSpawnChildren) {
+	begin_scoped_element(world, parent, element, None)
+}
+
+fn begin_scoped_element(
+	world: &mut World,
+	parent: Entity,
+	element: &HtmlElement,
+	scope: Option<HtmlScope>,
+) -> (Entity, HtmlSpawnChildren) {
+	let HtmlElement {
+		tag,
+		name_id,
+		classes,
+		autofocus,
+		..
+	} = element;
+
+	let handler = world
+		.get_resource::<HtmlTagRegistry>()
+		.and_then(|registry| registry.get(tag.as_str()));
+	let inline = handler.as_ref().is_some_and(|handler| handler.inline());
+
+	let mut entity = if inline {
+		world.spawn(NodeStyleSheet::Inherited)
+	} else {
+		world.spawn((Node::default(), NodeStyleSheet::Inherited))
+	};
+
+	if let Some(scope) = scope {
+		entity.insert(scope);
+	}
+
+	if let Some(name) = name_id {
+		entity.insert(Name::new(name.clone()));
+	}
+
+	if *autofocus {
+		entity.insert(AutoFocus);
+	}
+
+	if !classes.is_empty() {
+		entity.insert(ClassList::new(classes.join(" ").as_str()));
+	}
+
+	insert_direction(&mut entity, element);
+
+	let entity_id = entity.id();
+
+	world.entity_mut(parent).add_child(entity_id);
+
+	// Elements inherit the stylesheet of the root, unless `stylesheet` switches it for a subtree.
+	if let Some(style_sheet) = element
+		.attribute("stylesheet")
+		.and_then(|path| load_attribute_asset(world, entity_id, path))
+	{
+		world
+			.entity_mut(entity_id)
+			.insert(NodeStyleSheet::new(style_sheet));
+	}
 
-			match tag {
-				HtmlTag::VBox => {
-					entity.insert((
-						Node {
-							display: Display::Flex,
-							flex_direction: FlexDirection::Column,
-							row_gap: *gap,
-							..default()
-						},
-						Pickable::IGNORE,
-					));
-				}
-				HtmlTag::HBox => {
-					entity.insert((
-						Node {
-							display: Display::Flex,
-							flex_direction: FlexDirection::Row,
-							column_gap: *gap,
-							..default()
-						},
-						Pickable::IGNORE,
-					));
-				}
-				HtmlTag::Node => {}
-				HtmlTag::Button => {
-					entity.insert((
-						Button,
-						AutoDirectionalNavigation::default(),
-						Pickable {
-							is_hoverable: true,
-							should_block_lower: true,
-						},
-					));
-				}
-				HtmlTag::Label => {
-					entity.insert(Pickable::IGNORE);
-				}
-				HtmlTag::Spacer => {
-					entity.insert((
-						Node {
-							width: Val::Percent(100.0),
-							height: Val::Percent(100.0),
-							..default()
-						},
-						Pickable::IGNORE,
-					));
-				}
-				HtmlTag::Ui => {
-					entity.insert((
-						Node {
-							position_type: PositionType::Absolute,
-							display: Display::Flex,
-							flex_direction: FlexDirection::Column,
-							row_gap: *gap,
-							width: Val::Percent(100.0),
-							height: Val::Percent(100.0),
-							..default()
-						},
-						Pickable::IGNORE,
-					));
-				}
+	let translated = translate_attributes(world, entity_id, element);
+	let element = translated.as_ref().unwrap_or(element);
+
+	let spawn_children = if let Some(handler) = &handler {
+		handler.insert(&mut world.entity_mut(entity_id), element);
+		handler.spawn_children()
+	} else {
+		warn!("unknown tag `{}`", tag.as_str());
+		true
+	};
+	if !inline {
+		insert_background_image(&mut world.entity_mut(entity_id), element);
+		insert_grid_placement(&mut world.entity_mut(entity_id), element);
+		let consumed = handler
+			.as_ref()
+			.map_or(&[][..], |handler| handler.attributes());
+		insert_layout_attributes(&mut world.entity_mut(entity_id), element, consumed);
+	}
+	// Parsed by bevy_flair, which reports invalid declarations and applies the rest over any
+	// stylesheet rules, including those a handler set such as the `value` of `<color>`.
+	if let Some(style) = element.attribute("style") {
+		let mut entity = world.entity_mut(entity_id);
+		if let Some(mut inline_style) = entity.get_mut::<InlineStyle>() {
+			for (name, value) in style_declarations(style) {
+				inline_style.set(name.to_owned(), value.to_owned());
 			}
+		} else {
+			entity.insert(
+				style_declarations(style)
+					.map(|(name, value)| (name, value.to_owned()))
+					.collect::<InlineStyle>(),
+			);
+		}
+	}
 
-			let entity_id = entity.id();
+	let translated_text = insert_translated_text(world, entity_id, element);
+	let children = HtmlSpawnChildren {
+		text: spawn_children && !translated_text,
+		elements: spawn_children,
+	};
+	(entity_id, children)
+}
 
-			world.entity_mut(parent).add_child(entity_id);
+/// Finishes an element started with [`begin_element`] once its children are spawned.
+#[doc(hidden)]
+pub fn end_element(world: &mut World, entity_id: Entity, element: &HtmlElement) {
+	apply_direction(&mut world.entity_mut(entity_id));
 
-			for child in children {
-				spawn_node(world, entity_id, child);
+	if let Some(cb_key) = &element.callback {
+		world.resource_scope(|world: &mut World, resource: Mut<HtmlCssUiResource>| {
+			if let Some(cb) = resource.callbacks.get(cb_key) {
+				_ = cb(world, entity_id);
 			}
+		});
+	}
+}
+
+/// Loads the asset at the `path` of an attribute such as `src`, after interpolating it for
+/// `entity`, or returns `None` if it is empty.
+///
+/// The loader roots the paths it resolves with a leading `/`, which is stripped here, while
+/// interpolated paths are relative to the assets folder.
+pub(crate) fn load_attribute_asset<A: Asset>(
+	world: &World,
+	entity: Entity,
+	path: &str,
+) -> Option<Handle<A>> {
+	let path = if has_interpolation(path) {
+		interpolate_for(world, entity, path)
+	} else {
+		path.to_owned()
+	};
+	let path = path.trim();
+	let path = path.strip_prefix('/').unwrap_or(path);
+	if path.is_empty() {
+		return None;
+	}
+	Some(world.resource::<AssetServer>().load(path.to_owned()))
+}
 
-			if let Some(cb_key) = callback {
-				world.resource_scope(|world: &mut World, resource: Mut<HtmlCssUiResource>| {
-					if let Some(cb) = resource.callbacks.get(cb_key) {
-						_ = cb(world, entity_id);
-					}
-				});
+/// The `name: value` declarations of a `style` attribute, split on the semicolons that are not
+/// within parentheses or quotes, such as in `url("data:image/png;base64,...")`.
+fn style_declarations(style: &str) -> impl Iterator<Item = (&str, &str)> {
+	let mut declarations = Vec::new();
+	let (mut start, mut depth, mut quote) = (0, 0_usize, None);
+	for (index, char) in style.char_indices() {
+		match (quote, char) {
+			(Some(open), _) if char == open => quote = None,
+			(None, '"' | '\'') => quote = Some(char),
+			(None, '(') => depth += 1,
+			(None, ')') => depth = depth.saturating_sub(1),
+			(None, ';') if depth == 0 => {
+				declarations.push(&style[start..index]);
+				start = index + 1;
 			}
+			_ => {}
 		}
 	}
+	declarations.push(&style[start..]);
+	// Like `InlineStyle::new`, a declaration without a value is kept for bevy_flair to report.
+	declarations
+		.into_iter()
+		.map(str::trim)
+		.filter(|declaration| !declaration.is_empty())
+		.map(|declaration| match declaration.split_once(':') {
+			Some((name, value)) => (name.trim(), value.trim()),
+			None => (declaration, ""),
+		})
+}
+
+#[cfg(test)]
+pub(crate) mod build_tests {
+	use std::{fmt::Debug, time::Duration};
+
+	use bevy::{
+		app::{HierarchyPropagatePlugin, PropagateSet},
+		camera::{ComputedCameraValues, NormalizedRenderTarget, RenderTargetInfo, Viewport},
+		ecs::message::Messages,
+		input::{
+			ButtonState,
+			keyboard::{Key, KeyboardInput, NativeKeyCode},
+		},
+		input_focus::InputFocus,
+		picking::{
+			backend::HitData,
+			events::Click,
+			pointer::{Location, PointerButton, PointerId},
+		},
+		prelude::*,
+		text::{CosmicFontSystem, SwashCache, TextPipeline},
+		transform::{
+			StaticTransformOptimizations,
+			systems::{mark_dirty_trees, propagate_parent_transforms, sync_simple_transforms},
+		},
+		ui::{
+			ComputedUiRenderTargetInfo, ComputedUiTargetCamera, ui_layout_system,
+			ui_surface::UiSurface, update::propagate_ui_target_cameras,
+		},
+	};
+
+	use bevy_flair::prelude::InlineStyle;
+
+	use super::spawn_node;
+	use crate::{
+		form::HtmlValueChanged,
+		parser::parse_htmlish,
+		tags::{HtmlTagRegistry, register_builtin_tags},
+	};
+
+	/// Spawns the nodes of `source` under a root filling a 1000x100 camera, and lays them out.
+	pub(crate) fn spawn_laid_out(source: &str) -> (App, Entity) {
+		let mut app = App::new();
+		app.add_plugins((
+			HierarchyPropagatePlugin::<ComputedUiTargetCamera>::new(PostUpdate),
+			HierarchyPropagatePlugin::<ComputedUiRenderTargetInfo>::new(PostUpdate),
+		))
+		.init_resource::<UiScale>()
+		.init_resource::<UiSurface>()
+		.init_resource::<TextPipeline>()
+		.init_resource::<CosmicFontSystem>()
+		.init_resource::<SwashCache>()
+		.init_resource::<StaticTransformOptimizations>()
+		.init_resource::<HtmlTagRegistry>()
+		.add_systems(
+			PostUpdate,
+			(
+				ApplyDeferred,
+				propagate_ui_target_cameras,
+				ui_layout_system,
+				mark_dirty_trees,
+				sync_simple_transforms,
+				propagate_parent_transforms,
+			)
+				.chain(),
+		)
+		.configure_sets(
+			PostUpdate,
+			(
+				PropagateSet::<ComputedUiTargetCamera>::default(),
+				PropagateSet::<ComputedUiRenderTargetInfo>::default(),
+			)
+				.after(propagate_ui_target_cameras)
+				.before(ui_layout_system),
+		);
+		register_builtin_tags(&mut app);
+
+		let world = app.world_mut();
+		// Pointer events only bubble up to parents once windows can be looked for.
+		world.register_component::<Window>();
+		world.spawn((
+			Camera2d,
+			Camera {
+				computed: ComputedCameraValues {
+					target_info: Some(RenderTargetInfo {
+						physical_size: UVec2::new(1000, 100),
+						scale_factor: 1.0,
+					}),
+					..default()
+				},
+				viewport: Some(Viewport {
+					physical_size: UVec2::new(1000, 100),
+					..default()
+				}),
+				..default()
+			},
+		));
+		let root = world
+			.spawn(Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				align_items: AlignItems::Start,
+				..default()
+			})
+			.id();
+		for node in &parse_htmlish(&source.into()).expect("test document failed to parse") {
+			spawn_node(world, root, node);
+		}
+
+		app.update();
+		(app, root)
+	}
+
+	/// The horizontal extent of `entity` in the layout.
+	pub(crate) fn x_range(world: &World, entity: Entity) -> (f32, f32) {
+		let node = world.get::<ComputedNode>(entity).expect("not laid out");
+		let center = world
+			.get::<UiGlobalTransform>(entity)
+			.expect("not laid out")
+			.translation
+			.x;
+		let half = node.size().x / 2.0;
+		(center - half, center + half)
+	}
+
+	/// Like [`spawn_laid_out`], with the focus and messages that form elements use.
+	pub(crate) fn spawn_form(source: &str) -> (App, Entity) {
+		let (mut app, root) = spawn_laid_out(source);
+		app.init_resource::<InputFocus>()
+			.add_message::<KeyboardInput>()
+			.add_message::<HtmlValueChanged>();
+		(app, root)
+	}
+
+	/// Sends a press of `key`, as the keyboard would, to be read during the next update.
+	pub(crate) fn press_key(app: &mut App, key: Key) {
+		let text = match &key {
+			Key::Character(text) => Some(text.clone()),
+			_ => None,
+		};
+		app.world_mut().write_message(KeyboardInput {
+			key_code: KeyCode::Unidentified(NativeKeyCode::Unidentified),
+			logical_key: key,
+			state: ButtonState::Pressed,
+			text,
+			repeat: false,
+			window: Entity::PLACEHOLDER,
+		});
+	}
+
+	/// The [`HtmlValueChanged`] messages sent during the last update.
+	pub(crate) fn changed_values(app: &App) -> Vec<HtmlValueChanged> {
+		app.world()
+			.resource::<Messages<HtmlValueChanged>>()
+			.iter_current_update_messages()
+			.cloned()
+			.collect()
+	}
+
+	/// A mouse pointer event targeting `entity`, as picking would trigger it.
+	pub(crate) fn pointer<E: Debug + Clone + Reflect>(entity: Entity, event: E) -> Pointer<E> {
+		let location = Location {
+			target: NormalizedRenderTarget::None {
+				width: 1000,
+				height: 100,
+			},
+			position: Vec2::ZERO,
+		};
+		Pointer::new(PointerId::Mouse, location, event, entity)
+	}
+
+	/// A picking hit with no position, for the events made by [`pointer`].
+	pub(crate) fn hit() -> HitData {
+		HitData::new(Entity::PLACEHOLDER, 0.0, None, None)
+	}
+
+	/// Clicks `entity` with the primary mouse button, applying the commands of the observers.
+	pub(crate) fn click(app: &mut App, entity: Entity) {
+		app.world_mut().trigger(pointer(
+			entity,
+			Click {
+				button: PointerButton::Primary,
+				hit: hit(),
+				duration: Duration::ZERO,
+			},
+		));
+		app.world_mut().flush();
+	}
+
+	/// Only elements that spawn parts ahead of their content, such as `<checkbox>`, move their text
+	/// into runs, while others keep it as their own `Text`.
+	#[test]
+	fn test_text_next_to_nodes() {
+		let (app, root) =
+			spawn_laid_out("<button><node></node>Play</button><checkbox>Sound</checkbox>");
+		let world = app.world();
+		let children = world.get::<Children>(root).unwrap();
+		let (button, checkbox) = (children[0], children[1]);
+
+		assert_eq!(world.get::<Text>(button).unwrap().0, "Play");
+		assert_eq!(world.get::<Children>(button).unwrap().len(), 1);
+
+		assert!(world.get::<Text>(checkbox).is_none());
+		let run = *world.get::<Children>(checkbox).unwrap().last().unwrap();
+		assert_eq!(world.get::<Text>(run).unwrap().0, "Sound");
+	}
+
+	/// The `style` attribute becomes the inline style of an element, or is merged into the one its
+	/// handler set, keeping semicolons within values.
+	#[test]
+	fn test_style_attribute() {
+		let (mut app, root) = spawn_laid_out(
+			r#"<node style="background-image: url('data:image/png;base64,AA=='); width: 10px;"></node>
+			<label><color value="red" style="font-size: 20px; color: blue">Hi</color></label>"#,
+		);
+		let children = app.world().get::<Children>(root).unwrap().to_vec();
+		let color = app.world().get::<Children>(children[1]).unwrap()[0];
+		let world = app.world_mut();
+		assert_eq!(
+			*world.get::<InlineStyle>(children[0]).unwrap(),
+			InlineStyle::from_iter([
+				("background-image", "url('data:image/png;base64,AA==')"),
+				("width", "10px"),
+			])
+		);
+		let mut style = world.get_mut::<InlineStyle>(color).unwrap();
+		assert_eq!(style.get("color"), Some("blue"));
+		assert_eq!(style.get("font-size"), Some("20px"));
+	}
 }
@@ -0,0 +1,373 @@
+use bevy::{
+	input_focus::InputFocus,
+	prelude::*,
+	ui::{
+		Checkable, Checked, InteractionDisabled,
+		auto_directional_navigation::AutoDirectionalNavigation,
+	},
+};
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::HtmlElement,
+	data::HtmlValue,
+	form::{ConfirmInput, HtmlValueChanged},
+	span::HtmlTextAfterParts,
+	tags::HtmlTagHandler,
+};
+
+/// Marks a `<checkbox>` or `<toggle>`, which is checked while it has bevy's [`Checked`] component.
+///
+/// Flair matches checked elements with `:checked`, and disabled ones (with bevy's
+/// [`InteractionDisabled`]) with `:disabled`.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct HtmlCheckbox;
+
+/// The part of a `<checkbox>`, `<toggle>` or `<radio>` that shows whether it is checked.
+#[derive(Component)]
+pub(crate) enum HtmlCheckboxParts {
+	/// The mark inside a checkbox's box or a radio's circle, hidden while unchecked.
+	Mark(Entity),
+	/// The track of a toggle, with its knob at the start while unchecked and at the end while checked.
+	Track(Entity),
+}
+
+/// `<checkbox checked>Label</checkbox>`, a box with a check mark followed by its children.
+///
+/// Drawn with the classes `checkbox-box` and `checkbox-mark` for styling.
+#[derive(Default)]
+pub(crate) struct CheckboxTag;
+
+impl HtmlTagHandler for CheckboxTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let parts = entity.world_scope(|world| {
+			let mark = world
+				.spawn((
+					Node {
+						width: Val::Px(8.0),
+						height: Val::Px(8.0),
+						..default()
+					},
+					BackgroundColor(Color::WHITE),
+					ClassList::new("checkbox-mark"),
+					Pickable::IGNORE,
+				))
+				.id();
+			let check_box = world
+				.spawn((
+					Node {
+						width: Val::Px(16.0),
+						height: Val::Px(16.0),
+						border: UiRect::all(Val::Px(2.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						flex_shrink: 0.0,
+						..default()
+					},
+					BorderColor::all(Color::WHITE),
+					ClassList::new("checkbox-box"),
+					Pickable::IGNORE,
+				))
+				.add_child(mark)
+				.id();
+			(check_box, HtmlCheckboxParts::Mark(mark))
+		});
+		insert_checkbox(entity, element, parts);
+	}
+}
+
+/// `<toggle checked>Label</toggle>`, a switch with a sliding knob followed by its children.
+///
+/// Drawn with the classes `toggle-track` and `toggle-knob` for styling.
+#[derive(Default)]
+pub(crate) struct ToggleTag;
+
+impl HtmlTagHandler for ToggleTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let parts = entity.world_scope(|world| {
+			let knob = world
+				.spawn((
+					Node {
+						width: Val::Px(12.0),
+						height: Val::Px(12.0),
+						border_radius: BorderRadius::MAX,
+						..default()
+					},
+					BackgroundColor(Color::WHITE),
+					ClassList::new("toggle-knob"),
+					Pickable::IGNORE,
+				))
+				.id();
+			let track = world
+				.spawn((
+					Node {
+						width: Val::Px(32.0),
+						height: Val::Px(20.0),
+						border: UiRect::all(Val::Px(2.0)),
+						padding: UiRect::all(Val::Px(2.0)),
+						border_radius: BorderRadius::MAX,
+						align_items: AlignItems::Center,
+						flex_shrink: 0.0,
+						..default()
+					},
+					BorderColor::all(Color::WHITE),
+					ClassList::new("toggle-track"),
+					Pickable::IGNORE,
+				))
+				.add_child(knob)
+				.id();
+			(track, HtmlCheckboxParts::Track(track))
+		});
+		insert_checkbox(entity, element, parts);
+	}
+}
+
+fn insert_checkbox(
+	entity: &mut EntityWorldMut,
+	element: &HtmlElement,
+	(indicator, parts): (Entity, HtmlCheckboxParts),
+) {
+	entity.add_child(indicator).insert((
+		Node {
+			display: Display::Flex,
+			flex_direction: FlexDirection::Row,
+			align_items: AlignItems::Center,
+			column_gap: element.gap,
+			..default()
+		},
+		HtmlCheckbox,
+		HtmlTextAfterParts,
+		Checkable,
+		parts,
+		AutoDirectionalNavigation::default(),
+		Pickable {
+			is_hoverable: true,
+			should_block_lower: true,
+		},
+	));
+	if element.attribute("checked").is_some() {
+		entity.insert(Checked);
+	}
+	if element.attribute("disabled").is_some() {
+		entity.insert(InteractionDisabled);
+	}
+}
+
+/// Flips a `<checkbox>` or `<toggle>` and reports the new value.
+fn toggle(
+	commands: &mut Commands,
+	changed: &mut MessageWriter<HtmlValueChanged>,
+	entity: Entity,
+	checked: bool,
+) {
+	if checked {
+		commands.entity(entity).remove::<Checked>();
+	} else {
+		commands.entity(entity).insert(Checked);
+	}
+	changed.write(HtmlValueChanged {
+		entity,
+		value: HtmlValue::Bool(!checked),
+	});
+}
+
+/// Toggles a clicked `<checkbox>` or `<toggle>` and focuses it.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_checkbox_click(
+	click: On<Pointer<Click>>,
+	checkboxes: Query<(Has<Checked>, Has<InteractionDisabled>), With<HtmlCheckbox>>,
+	mut focus: ResMut<InputFocus>,
+	mut commands: Commands,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	let Ok((checked, disabled)) = checkboxes.get(click.entity) else {
+		return;
+	};
+	focus.set(click.entity);
+	if !disabled {
+		toggle(&mut commands, &mut changed, click.entity, checked);
+	}
+}
+
+/// Toggles the focused `<checkbox>` or `<toggle>` when the user confirms.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_checkbox_confirm(
+	mut confirm: ConfirmInput,
+	focus: Res<InputFocus>,
+	checkboxes: Query<(Has<Checked>, Has<InteractionDisabled>), With<HtmlCheckbox>>,
+	mut commands: Commands,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	if !confirm.just_confirmed() {
+		return;
+	}
+	let Some(entity) = focus.get() else {
+		return;
+	};
+	if let Ok((checked, false)) = checkboxes.get(entity) {
+		toggle(&mut commands, &mut changed, entity, checked);
+	}
+}
+
+/// Shows the check mark, or moves the knob, of each `<checkbox>`, `<toggle>` or `<radio>` to match
+/// [`Checked`].
+pub(crate) fn html_checkbox_update_display(
+	checkboxes: Query<(&HtmlCheckboxParts, Has<Checked>)>,
+	mut nodes: Query<(&mut Node, &mut Visibility)>,
+) {
+	for (parts, checked) in &checkboxes {
+		match *parts {
+			HtmlCheckboxParts::Mark(mark) => {
+				if let Ok((_, mut visibility)) = nodes.get_mut(mark) {
+					visibility.set_if_neq(if checked {
+						Visibility::Inherited
+					} else {
+						Visibility::Hidden
+					});
+				}
+			}
+			HtmlCheckboxParts::Track(track) => {
+				if let Ok((mut node, _)) = nodes.get_mut(track) {
+					let justify = if checked {
+						JustifyContent::FlexEnd
+					} else {
+						JustifyContent::FlexStart
+					};
+					if node.justify_content != justify {
+						node.justify_content = justify;
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod checkbox_tests {
+	use bevy::{
+		input::keyboard::Key,
+		input_focus::InputFocus,
+		prelude::*,
+		ui::{Checked, InteractionDisabled},
+	};
+
+	use super::{
+		HtmlCheckboxParts, html_checkbox_click, html_checkbox_confirm, html_checkbox_update_display,
+	};
+	use crate::{
+		build::build_tests::{
+			changed_values, click, press_key, spawn_form, spawn_laid_out, x_range,
+		},
+		data::HtmlValue,
+		form::HtmlValueChanged,
+	};
+
+	#[test]
+	fn test_checkbox_label() {
+		let (mut app, root) = spawn_laid_out("<checkbox>Label</checkbox>");
+		let world = app.world_mut();
+		let checkbox = world.get::<Children>(root).unwrap()[0];
+		assert!(world.get::<Text>(checkbox).is_none());
+
+		let children: Vec<Entity> = world.get::<Children>(checkbox).unwrap().to_vec();
+		let [check_box, label] = children[..] else {
+			panic!("expected the box and the label, got {children:?}");
+		};
+		assert_eq!(
+			world.get::<Text>(label).map(|text| text.0.as_str()),
+			Some("Label")
+		);
+		assert!(world.get::<Node>(label).is_some());
+		assert_eq!(world.get::<Pickable>(label), Some(&Pickable::IGNORE));
+		assert!(x_range(world, label).0 >= x_range(world, check_box).1);
+	}
+
+	/// Clicking toggles a checkbox and focuses it, unless it is disabled, where it is only focused.
+	#[test]
+	fn test_checkbox_click() {
+		let (mut app, root) =
+			spawn_form("<checkbox>Sound</checkbox><checkbox disabled>Music</checkbox>");
+		app.add_observer(html_checkbox_click);
+		let children = app.world().get::<Children>(root).unwrap().to_vec();
+		let (sound, music) = (children[0], children[1]);
+
+		click(&mut app, sound);
+		assert!(app.world().entity(sound).contains::<Checked>());
+		assert_eq!(app.world().resource::<InputFocus>().get(), Some(sound));
+		assert_eq!(
+			changed_values(&app),
+			vec![HtmlValueChanged {
+				entity: sound,
+				value: HtmlValue::Bool(true),
+			}]
+		);
+
+		click(&mut app, sound);
+		assert!(!app.world().entity(sound).contains::<Checked>());
+
+		app.update();
+		assert!(app.world().entity(music).contains::<InteractionDisabled>());
+		click(&mut app, music);
+		assert!(!app.world().entity(music).contains::<Checked>());
+		assert_eq!(app.world().resource::<InputFocus>().get(), Some(music));
+		assert!(changed_values(&app).is_empty());
+	}
+
+	/// Confirming toggles the focused checkbox, unless it is disabled.
+	#[test]
+	fn test_checkbox_confirm() {
+		let (mut app, root) =
+			spawn_form("<toggle checked>Sound</toggle><checkbox disabled>Music</checkbox>");
+		app.add_systems(Update, html_checkbox_confirm);
+		let children = app.world().get::<Children>(root).unwrap().to_vec();
+		let (sound, music) = (children[0], children[1]);
+
+		app.world_mut().resource_mut::<InputFocus>().set(sound);
+		press_key(&mut app, Key::Enter);
+		app.update();
+		assert!(!app.world().entity(sound).contains::<Checked>());
+		assert_eq!(
+			changed_values(&app),
+			vec![HtmlValueChanged {
+				entity: sound,
+				value: HtmlValue::Bool(false),
+			}]
+		);
+
+		app.world_mut().resource_mut::<InputFocus>().set(music);
+		press_key(&mut app, Key::Space);
+		app.update();
+		assert!(!app.world().entity(music).contains::<Checked>());
+		assert!(changed_values(&app).is_empty());
+	}
+
+	/// The mark of a checkbox is shown while it is checked, and the knob of a toggle moves to the end.
+	#[test]
+	fn test_checkbox_update_display() {
+		let (mut app, root) = spawn_form("<checkbox>Sound</checkbox><toggle>Music</toggle>");
+		app.add_systems(Update, html_checkbox_update_display);
+		app.update();
+		let children = app.world().get::<Children>(root).unwrap().to_vec();
+		let (sound, music) = (children[0], children[1]);
+		let HtmlCheckboxParts::Mark(mark) = *app.world().get(sound).unwrap() else {
+			panic!("a checkbox has a mark");
+		};
+		let HtmlCheckboxParts::Track(track) = *app.world().get(music).unwrap() else {
+			panic!("a toggle has a track");
+		};
+		assert_eq!(app.world().get(mark), Some(&Visibility::Hidden));
+		assert_eq!(
+			app.world().get::<Node>(track).unwrap().justify_content,
+			JustifyContent::FlexStart
+		);
+
+		app.world_mut().entity_mut(sound).insert(Checked);
+		app.world_mut().entity_mut(music).insert(Checked);
+		app.update();
+		assert_eq!(app.world().get(mark), Some(&Visibility::Inherited));
+		assert_eq!(
+			app.world().get::<Node>(track).unwrap().justify_content,
+			JustifyContent::FlexEnd
+		);
+	}
+}
@@ -0,0 +1,513 @@
+use std::{
+	collections::HashMap,
+	fmt::Write as _,
+	fs,
+	path::{Component, Path, PathBuf},
+};
+
+use bevy::{asset::AssetPath, prelude::Val};
+
+use crate::{
+	ast::{HtmlElement, HtmlNode, HtmlTag},
+	error::HtmlUiError,
+	loader::{
+		AssetKind, MAX_INCLUDE_DEPTH, check_tags, collect_includes, expand_includes,
+		resolve_asset_attributes, style_css, take_styles,
+	},
+	parser::parse_htmlish,
+	tags::{HtmlTagNames, HtmlTagRegistry},
+	template::expand_templates,
+};
+
+/// Parses the HTML document at `path` (relative to the package root) from a build script and
+/// writes it out as Rust code for [`html_ui!`], so release builds do not parse markup at runtime.
+///
+/// `<include>`s are resolved relative to the including file, or to the `assets` folder if they start
+/// with `/`, and `<template>`s are expanded. If the document cannot be parsed, uses a tag that is
+/// neither built in nor a template, or has a `<link>`ed stylesheet or `<style>` block, the generated
+/// code is a `compile_error!` naming the problem, so the error shows up where `html_ui!` is used.
+///
+/// The generated code is a function spawning the entity tree of the document, wrapped in an
+/// [`HtmlUiAsset`](crate::HtmlUiAsset) so that it is used through
+/// [`HtmlCssUiResource`](crate::HtmlCssUiResource) like a loaded document. Each element still goes
+/// through its registered tag handler, so bindings and custom tags work identically in both kinds
+/// of build.
+///
+/// # Errors
+///
+/// Returns an error if `OUT_DIR` is not set, `path` is absolute or contains `..`, or the generated
+/// file cannot be written.
+pub fn build_html_ui(path: impl AsRef<Path>) -> std::io::Result<()> {
+	build_html_ui_with_tags(path, std::iter::empty::<String>())
+}
+
+/// Like [`build_html_ui`], but also accepts the custom tags in `tags`, which must be registered
+/// with [`HtmlUiAppExt::register_html_tag`](crate::HtmlUiAppExt::register_html_tag) at runtime.
+///
+/// # Errors
+///
+/// See [`build_html_ui`].
+pub fn build_html_ui_with_tags<S: Into<String>>(
+	path: impl AsRef<Path>,
+	tags: impl IntoIterator<Item = S>,
+) -> std::io::Result<()> {
+	let path = path.as_ref();
+	if !path
+		.components()
+		.all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+	{
+		return Err(std::io::Error::new(
+			std::io::ErrorKind::InvalidInput,
+			format!(
+				"{} must be relative to the package root, without `..`",
+				path.display()
+			),
+		));
+	}
+	let out_dir = std::env::var_os("OUT_DIR")
+		.map(PathBuf::from)
+		.ok_or_else(|| {
+			std::io::Error::other("OUT_DIR is not set, call this from a build script")
+		})?;
+
+	let mut files = Vec::new();
+	let registry = HtmlTagRegistry::builtin();
+	let names = registry.names();
+	for tag in tags {
+		names.insert(tag);
+	}
+	let code = match load_document(path, &names, &mut files) {
+		Ok(ast) => generate(&ast, &registry),
+		Err(err) => {
+			let message = format!("{}: {err}", path.display());
+			println!("cargo:warning={message}");
+			format!("::std::compile_error!({message:?})")
+		}
+	};
+
+	for file in files {
+		println!("cargo:rerun-if-changed={}", file.display());
+	}
+
+	let mut out_path = out_dir.join("html_ui").join(path);
+	out_path.as_mut_os_string().push(".rs");
+	if let Some(parent) = out_path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	fs::write(out_path, code)
+}
+
+fn load_document(
+	path: &Path,
+	tags: &HtmlTagNames,
+	files: &mut Vec<PathBuf>,
+) -> Result<Vec<HtmlNode>, HtmlUiError> {
+	let (ast, _) = load_document_with_templates(path, files, 0)?;
+	check_tags(&ast, tags)?;
+	Ok(ast)
+}
+
+fn load_document_with_templates(
+	path: &Path,
+	files: &mut Vec<PathBuf>,
+	depth: usize,
+) -> Result<(Vec<HtmlNode>, Vec<HtmlNode>), HtmlUiError> {
+	files.push(path.to_path_buf());
+	let source = fs::read_to_string(path).map_err(HtmlUiError::IoError)?;
+	let mut ast = parse_htmlish(&source)?;
+
+	let mut srcs = Vec::new();
+	collect_includes(&ast, &mut srcs)?;
+	if !srcs.is_empty() && depth >= MAX_INCLUDE_DEPTH {
+		return Err(HtmlUiError::IncludeError(
+			srcs.swap_remove(0),
+			"includes are nested too deeply".into(),
+		));
+	}
+
+	let mut included_asts = HashMap::new();
+	for src in srcs {
+		if included_asts.contains_key(&src) {
+			continue;
+		}
+		let include_path = match src.strip_prefix('/') {
+			Some(rooted) => Path::new("assets").join(rooted),
+			None => path.parent().unwrap_or(Path::new("")).join(&src),
+		};
+		let (included, templates) =
+			load_document_with_templates(&include_path, files, depth + 1)
+				.map_err(|err| HtmlUiError::IncludeError(src.clone(), err.to_string()))?;
+		let mut nodes = templates;
+		nodes.extend(included);
+		included_asts.insert(src, nodes);
+	}
+
+	if !included_asts.is_empty() {
+		ast = expand_includes(ast, &included_asts);
+	}
+
+	let (mut ast, mut templates) = expand_templates(ast)?;
+
+	// Asset paths are loaded relative to the `assets` folder at runtime.
+	let asset_path = AssetPath::from_path(path.strip_prefix("assets").unwrap_or(path)).into_owned();
+	let mut links = Vec::new();
+	resolve_asset_attributes(&mut ast, &asset_path, &mut |kind, path| {
+		if let AssetKind::Link = kind {
+			links.push(path.to_string());
+		}
+	})?;
+	resolve_asset_attributes(&mut templates, &asset_path, &mut |_, _| {})?;
+
+	// The `<link>`s and `<style>`s of included documents are left in place, so that they fail to
+	// compile in the including document as its own do.
+	if depth > 0 {
+		return Ok((ast, templates));
+	}
+	let css = style_css(&take_styles(&mut ast));
+	if let Some(link) = links.first() {
+		return Err(HtmlUiError::StyleSheetError(
+			link.clone(),
+			"`<link>` cannot be compiled into the document, pass the stylesheet to \
+			 `HtmlCssUiResource` instead"
+				.into(),
+		));
+	}
+	if !css.is_empty() {
+		return Err(HtmlUiError::ParseError(
+			"`<style>` cannot be compiled into the document, pass its CSS to `HtmlCssUiResource` \
+			 instead"
+				.into(),
+		));
+	}
+	Ok((ast, templates))
+}
+
+/// Writes `ast` as an expression building an `HtmlUiAsset` that is spawned by a generated function,
+/// with every type reached through `__html_ui`.
+///
+/// The function spawns each element with [`begin_element`](crate::__codegen::begin_element) and
+/// [`end_element`](crate::__codegen::end_element), and its text and element children in between, so
+/// the document is never walked at runtime. Only the children a tag may read as data are kept in the
+/// element passed to its handler, and `for` lists keep their whole element to respawn it from.
+pub(crate) fn generate(ast: &[HtmlNode], registry: &HtmlTagRegistry) -> String {
+	let mut out = String::from(
+		"__html_ui::HtmlUiAsset::compiled(|world: &mut __html_ui::World, e0: __html_ui::Entity| {\n",
+	);
+	for node in ast {
+		write_spawn(&mut out, node, 0, registry);
+	}
+	out.push_str("})\n");
+	out
+}
+
+/// Writes the statements spawning `node` under the entity `e{depth}`, one per line.
+fn write_spawn(out: &mut String, node: &HtmlNode, depth: usize, registry: &HtmlTagRegistry) {
+	let element = match node {
+		HtmlNode::Text(text) => {
+			let _ = writeln!(out, "__html_ui::spawn_text(world, e{depth}, {text:?});");
+			return;
+		}
+		HtmlNode::Element(element) => element,
+	};
+
+	if element.each.is_some() {
+		let _ = write!(out, "__html_ui::spawn_node(world, e{depth}, &");
+		write_node(out, node);
+		out.push_str(");\n");
+		return;
+	}
+
+	let child = depth + 1;
+	let children = if element.children.is_empty() {
+		"_"
+	} else {
+		"children"
+	};
+	out.push_str("{\nlet element = ");
+	write_element(out, element, &data_children(element, registry));
+	let _ = writeln!(
+		out,
+		";\nlet (e{child}, {children}) = __html_ui::begin_element(world, e{depth}, &element);"
+	);
+	for node in &element.children {
+		match node {
+			HtmlNode::Text(text) => {
+				let _ = writeln!(
+					out,
+					"if children.text() {{ __html_ui::spawn_text(world, e{child}, {text:?}); }}"
+				);
+			}
+			HtmlNode::Element(_) => {
+				out.push_str("if children.elements() {\n");
+				write_spawn(out, node, child, registry);
+				out.push_str("}\n");
+			}
+		}
+	}
+	let _ = writeln!(
+		out,
+		"__html_ui::end_element(world, e{child}, &element);\n}}"
+	);
+}
+
+/// The children of `element` its handler may read: all of them for custom tags, which are not in
+/// `registry`, and for the tags that read their children as data instead of spawning them, such as
+/// `<select>`, and otherwise only the text, as `<option>` and translated text fall back to it.
+fn data_children(element: &HtmlElement, registry: &HtmlTagRegistry) -> Vec<HtmlNode> {
+	if registry
+		.get(element.tag.as_str())
+		.is_none_or(|handler| !handler.spawn_children())
+	{
+		return element.children.clone();
+	}
+	element
+		.children
+		.iter()
+		.filter(|node| matches!(node, HtmlNode::Text(_)))
+		.cloned()
+		.collect()
+}
+
+fn write_nodes(out: &mut String, nodes: &[HtmlNode]) {
+	out.push_str("::std::vec![");
+	for node in nodes {
+		write_node(out, node);
+		out.push(',');
+	}
+	out.push(']');
+}
+
+fn write_node(out: &mut String, node: &HtmlNode) {
+	match node {
+		HtmlNode::Text(text) => {
+			let _ = write!(out, "__html_ui::HtmlNode::Text({})", string(text));
+		}
+		HtmlNode::Element(element) => {
+			out.push_str("__html_ui::HtmlNode::Element(");
+			write_element(out, element, &element.children);
+			out.push(')');
+		}
+	}
+}
+
+fn write_element(out: &mut String, element: &HtmlElement, children: &[HtmlNode]) {
+	let HtmlElement {
+		tag,
+		name_id,
+		classes,
+		gap,
+		autofocus,
+		callback,
+		each,
+		attributes,
+		children: _,
+	} = element;
+
+	let _ = write!(
+		out,
+		"__html_ui::HtmlElement {{ tag: {}, name_id: {}, classes: ::std::vec![",
+		tag_expr(tag),
+		option_string(name_id.as_deref()),
+	);
+	for class in classes {
+		let _ = write!(out, "{},", string(class));
+	}
+	let _ = write!(
+		out,
+		"], gap: {}, autofocus: {autofocus}, callback: {}, each: ",
+		val_expr(*gap),
+		option_string(callback.as_deref()),
+	);
+	match each {
+		Some(each) => {
+			let _ = write!(
+				out,
+				"::std::option::Option::Some(__html_ui::HtmlEach {{ binding: {}, collection: {}, key: {} }})",
+				string(&each.binding),
+				string(&each.collection),
+				option_string(each.key.as_deref()),
+			);
+		}
+		None => out.push_str("::std::option::Option::None"),
+	}
+	out.push_str(", attributes: ::std::vec![");
+	for (name, value) in attributes {
+		let _ = write!(out, "({}, {}),", string(name), string(value));
+	}
+	out.push_str("], children: ");
+	write_nodes(out, children);
+	out.push_str(" }");
+}
+
+fn string(value: &str) -> String {
+	format!("::std::string::String::from({value:?})")
+}
+
+fn option_string(value: Option<&str>) -> String {
+	match value {
+		Some(value) => format!("::std::option::Option::Some({})", string(value)),
+		None => "::std::option::Option::None".to_owned(),
+	}
+}
+
+fn tag_expr(tag: &HtmlTag) -> String {
+	let variant = match tag {
+		HtmlTag::Include => "Include",
+		HtmlTag::Template => "Template",
+		HtmlTag::Slot => "Slot",
+		HtmlTag::Style => "Style",
+		HtmlTag::Link => "Link",
+		HtmlTag::Named(name) => {
+			return format!("__html_ui::HtmlTag::Named({})", string(name));
+		}
+	};
+	format!("__html_ui::HtmlTag::{variant}")
+}
+
+fn val_expr(val: Val) -> String {
+	match val {
+		Val::Auto => "__html_ui::Val::Auto".to_owned(),
+		Val::Px(value) => format!("__html_ui::Val::Px({value:?})"),
+		Val::Percent(value) => format!("__html_ui::Val::Percent({value:?})"),
+		Val::Vw(value) => format!("__html_ui::Val::Vw({value:?})"),
+		Val::Vh(value) => format!("__html_ui::Val::Vh({value:?})"),
+		Val::VMin(value) => format!("__html_ui::Val::VMin({value:?})"),
+		Val::VMax(value) => format!("__html_ui::Val::VMax({value:?})"),
+	}
+}
+
+#[cfg(test)]
+mod codegen_tests {
+	use std::{fs, io::ErrorKind, path::Path};
+
+	use bevy::{ecs::component::ComponentInfo, prelude::*};
+
+	use crate::{
+		asset::HtmlUiAsset,
+		build::spawn_node,
+		parser::parse_htmlish,
+		resources::HtmlCssUiResource,
+		tags::{HtmlTagNames, HtmlTagRegistry, HtmlUiAppExt, NodeTag, register_builtin_tags},
+	};
+
+	use super::{build_html_ui, generate, load_document};
+
+	/// The built-in tags, and `custom`.
+	fn tag_names(custom: &[&str]) -> HtmlTagNames {
+		let names = HtmlTagRegistry::builtin().names();
+		for tag in custom {
+			names.insert(*tag);
+		}
+		names
+	}
+
+	const FIXTURE: &str = "tests/codegen/menu.html";
+
+	/// Compiles the code generated for the fixture, as `html_ui!` would, and checks that it spawns
+	/// the same entity tree as parsing it. `menu.html.rs` is checked against the current output, so
+	/// it must be regenerated whenever that changes.
+	#[test]
+	fn test_generated_asset() {
+		let tags = tag_names(&["health-bar"]);
+		let code = generate(
+			&load_document(Path::new(FIXTURE), &tags, &mut Vec::new()).unwrap(),
+			&HtmlTagRegistry::builtin(),
+		);
+		assert_eq!(
+			code,
+			include_str!("../tests/codegen/menu.html.rs"),
+			"tests/codegen/menu.html.rs is out of date"
+		);
+
+		let asset: HtmlUiAsset = {
+			use crate::__codegen as __html_ui;
+			include!("../tests/codegen/menu.html.rs")
+		};
+		assert!(asset.ast.is_empty());
+
+		let mut app = App::new();
+		app.init_resource::<HtmlTagRegistry>()
+			.init_resource::<HtmlCssUiResource>();
+		register_builtin_tags(&mut app);
+		app.register_html_tag::<NodeTag>("health-bar");
+		let world = app.world_mut();
+
+		let compiled = world.spawn(Node::default()).id();
+		(asset.spawn.unwrap())(world, compiled);
+		let parsed = world.spawn(Node::default()).id();
+		let source = fs::read_to_string(FIXTURE).unwrap();
+		for node in &parse_htmlish(&source).unwrap() {
+			spawn_node(world, parsed, node);
+		}
+		assert_eq!(describe(world, compiled), describe(world, parsed));
+	}
+
+	/// The components of `entity` and its descendants in order, with their text and layout.
+	fn describe(world: &World, entity: Entity) -> Vec<String> {
+		let mut components: Vec<_> = world
+			.inspect_entity(entity)
+			.unwrap()
+			.map(ComponentInfo::id)
+			.collect();
+		components.sort();
+		let mut lines = vec![format!(
+			"{components:?} {:?} {:?}",
+			world.get::<Text>(entity),
+			world.get::<Node>(entity)
+		)];
+		for child in world.get::<Children>(entity).into_iter().flatten() {
+			lines.extend(describe(world, *child));
+		}
+		lines
+	}
+
+	#[test]
+	fn test_unknown_tags() {
+		let path = Path::new(FIXTURE);
+		let err = load_document(path, &tag_names(&[]), &mut Vec::new()).unwrap_err();
+		assert!(err.to_string().contains("<health-bar>"), "{err}");
+	}
+
+	#[test]
+	fn test_style() {
+		for path in [
+			"tests/codegen/style.html",
+			"tests/codegen/include_style.html",
+		] {
+			let err = load_document(Path::new(path), &tag_names(&[]), &mut Vec::new()).unwrap_err();
+			assert!(err.to_string().contains("`<style>`"), "{path}: {err}");
+		}
+	}
+
+	#[test]
+	fn test_link() {
+		for path in ["tests/codegen/link.html", "tests/codegen/include_link.html"] {
+			let err = load_document(Path::new(path), &tag_names(&[]), &mut Vec::new()).unwrap_err();
+			assert!(err.to_string().contains("`<link>`"), "{path}: {err}");
+		}
+	}
+
+	#[test]
+	fn test_escaping_paths() {
+		for path in ["/tmp/menu.html", "../menu.html", "assets/../../menu.html"] {
+			let err = build_html_ui(path).unwrap_err();
+			assert_eq!(err.kind(), ErrorKind::InvalidInput, "{path}");
+		}
+	}
+
+	#[test]
+	fn test_generate() {
+		let ast = parse_htmlish(
+			&r#"<ui class="a"><vbox gap="4px"><label id="l">Say "hi"</label></vbox></ui>"#.into(),
+		)
+		.unwrap();
+		let code = generate(&ast, &HtmlTagRegistry::builtin());
+		assert!(code.starts_with("__html_ui::HtmlUiAsset::compiled("));
+		assert!(
+			code.contains("tag: __html_ui::HtmlTag::Named(::std::string::String::from(\"vbox\"))")
+		);
+		assert!(code.contains("gap: __html_ui::Val::Px(4.0)"));
+		assert!(code.contains("__html_ui::begin_element(world, e1, &element)"));
+		assert!(code.contains(r#"__html_ui::spawn_text(world, e3, "Say \"hi\"");"#));
+	}
+}
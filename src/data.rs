@@ -0,0 +1,223 @@
+use std::{collections::BTreeMap, fmt};
+
+use bevy::prelude::*;
+
+/// A value that can be bound into an HTML document, either through `{{ path }}` interpolation or
+/// as the collection of a `for` loop.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum HtmlValue {
+	#[default]
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	List(Vec<HtmlValue>),
+	Map(BTreeMap<String, HtmlValue>),
+}
+
+impl HtmlValue {
+	#[must_use]
+	pub fn map<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> Self
+	where
+		K: Into<String>,
+		V: Into<HtmlValue>,
+	{
+		Self::Map(
+			entries
+				.into_iter()
+				.map(|(key, value)| (key.into(), value.into()))
+				.collect(),
+		)
+	}
+
+	/// Looks up a single map key or list index.
+	#[must_use]
+	pub fn get(&self, key: &str) -> Option<&HtmlValue> {
+		match self {
+			Self::Map(map) => map.get(key),
+			Self::List(list) => key.parse::<usize>().ok().and_then(|index| list.get(index)),
+			_ => None,
+		}
+	}
+
+	/// Looks up a dot-separated path such as `items.0.name`.
+	#[must_use]
+	pub fn get_path(&self, path: &str) -> Option<&HtmlValue> {
+		path.split('.')
+			.try_fold(self, |value, key| value.get(key.trim()))
+	}
+}
+
+impl fmt::Display for HtmlValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Null | Self::Map(_) => Ok(()),
+			Self::Bool(value) => value.fmt(f),
+			Self::Number(value) => value.fmt(f),
+			Self::String(value) => value.fmt(f),
+			Self::List(list) => {
+				for (i, value) in list.iter().enumerate() {
+					if i > 0 {
+						f.write_str(", ")?;
+					}
+					value.fmt(f)?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+macro_rules! impl_from_number {
+	($($ty:ty),*) => {
+		$(
+			impl From<$ty> for HtmlValue {
+				#[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+				fn from(value: $ty) -> Self {
+					Self::Number(value as f64)
+				}
+			}
+		)*
+	};
+}
+
+impl_from_number!(f32, f64, i32, i64, u32, u64, usize);
+
+impl From<bool> for HtmlValue {
+	fn from(value: bool) -> Self {
+		Self::Bool(value)
+	}
+}
+
+impl From<&str> for HtmlValue {
+	fn from(value: &str) -> Self {
+		Self::String(value.to_owned())
+	}
+}
+
+impl From<String> for HtmlValue {
+	fn from(value: String) -> Self {
+		Self::String(value)
+	}
+}
+
+impl<T: Into<HtmlValue>> From<Vec<T>> for HtmlValue {
+	fn from(value: Vec<T>) -> Self {
+		Self::List(value.into_iter().map(Into::into).collect())
+	}
+}
+
+impl<T: Into<HtmlValue>> From<Option<T>> for HtmlValue {
+	fn from(value: Option<T>) -> Self {
+		value.map_or(Self::Null, Into::into)
+	}
+}
+
+/// The data that documents can read through `{{ path }}` interpolation and `for` loops.
+///
+/// Modifying this resource updates every bound text and list in the spawned UI without respawning it.
+#[derive(Default, Resource)]
+pub struct HtmlUiData {
+	values: BTreeMap<String, HtmlValue>,
+}
+
+impl HtmlUiData {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[must_use]
+	pub fn with(mut self, key: impl Into<String>, value: impl Into<HtmlValue>) -> Self {
+		self.set(key, value);
+		self
+	}
+
+	pub fn set(&mut self, key: impl Into<String>, value: impl Into<HtmlValue>) {
+		self.values.insert(key.into(), value.into());
+	}
+
+	pub fn remove(&mut self, key: &str) -> Option<HtmlValue> {
+		self.values.remove(key)
+	}
+
+	/// Looks up a dot-separated path such as `inventory.items`.
+	#[must_use]
+	pub fn get(&self, path: &str) -> Option<&HtmlValue> {
+		let (head, rest) = split_path(path);
+		let value = self.values.get(head)?;
+		match rest {
+			Some(rest) => value.get_path(rest),
+			None => Some(value),
+		}
+	}
+}
+
+/// Splits `a.b.c` into `("a", Some("b.c"))`.
+pub(crate) fn split_path(path: &str) -> (&str, Option<&str>) {
+	match path.trim().split_once('.') {
+		Some((head, rest)) => (head, Some(rest)),
+		None => (path.trim(), None),
+	}
+}
+
+pub(crate) fn has_interpolation(text: &str) -> bool {
+	text.contains("{{")
+}
+
+/// Replaces every `{{ path }}` in `template` with the value returned by `lookup`, or with nothing
+/// if the path does not resolve.
+pub(crate) fn interpolate(
+	template: &str,
+	mut lookup: impl FnMut(&str) -> Option<HtmlValue>,
+) -> String {
+	let mut out = String::with_capacity(template.len());
+	let mut rest = template;
+
+	while let Some(start) = rest.find("{{") {
+		out.push_str(&rest[..start]);
+		let after = &rest[start + 2..];
+		let Some(end) = after.find("}}") else {
+			out.push_str(&rest[start..]);
+			return out;
+		};
+		if let Some(value) = lookup(after[..end].trim()) {
+			out.push_str(&value.to_string());
+		}
+		rest = &after[end + 2..];
+	}
+
+	out.push_str(rest);
+	out
+}
+
+#[cfg(test)]
+mod data_tests {
+	use super::{HtmlUiData, HtmlValue, interpolate};
+
+	#[test]
+	fn test_paths() {
+		let data = HtmlUiData::new().with(
+			"inventory",
+			HtmlValue::map([(
+				"items",
+				vec![HtmlValue::map([("id", 7)]), HtmlValue::map([("id", 9)])],
+			)]),
+		);
+		assert_eq!(
+			data.get("inventory.items.1.id"),
+			Some(&HtmlValue::Number(9.0))
+		);
+		assert!(data.get("inventory.items.2").is_none());
+		assert!(data.get("missing").is_none());
+	}
+
+	#[test]
+	fn test_interpolate() {
+		let data = HtmlUiData::new().with("name", "Amy").with("gold", 12);
+		let lookup = |path: &str| data.get(path).cloned();
+		assert_eq!(interpolate("Hi {{ name }}!", lookup), "Hi Amy!");
+		assert_eq!(interpolate("{{gold}} gold{{missing}}", lookup), "12 gold");
+		assert_eq!(interpolate("broken {{ name", lookup), "broken {{ name");
+	}
+}
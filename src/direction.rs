@@ -0,0 +1,644 @@
+use bevy::{
+	log::warn,
+	prelude::*,
+	reflect::{PartialReflect, ReflectMut, ReflectRef},
+};
+use bevy_flair::{prelude::*, style::components::NodeStyleMarker};
+
+use crate::{ast::HtmlElement, build::HtmlUiRoot};
+
+/// The direction rows and text flow in. As a resource it sets the direction of the whole UI, and
+/// `dir="rtl"` or `dir="ltr"` sets it for an element and its descendants.
+///
+/// Right-to-left elements are mirrored when they are spawned: rows are reversed, start and end
+/// alignment and the left and right sides of padding, margin, border and position are swapped, the
+/// columns of a `<grid>` and the `column` of its items are reversed, and text is right-justified.
+/// Changing the resource mirrors or un-mirrors every document in place.
+///
+/// Stylesheets are written left-to-right too, and the properties they set are mirrored in the same
+/// way, so that `padding-left` pads the right of a right-to-left element. Items a `<grid>` places
+/// automatically still fill its reversed columns from the left, so they need a `column` to be
+/// mirrored.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HtmlDirection {
+	#[default]
+	Ltr,
+	Rtl,
+}
+
+impl HtmlDirection {
+	#[must_use]
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Ltr => "ltr",
+			Self::Rtl => "rtl",
+		}
+	}
+}
+
+/// Inserts the direction of the `dir` attribute on `entity`, which stylesheets can also select as
+/// `[dir="rtl"]`.
+pub(crate) fn insert_direction(entity: &mut EntityWorldMut, element: &HtmlElement) {
+	let direction = match element.attribute("dir").map(str::trim) {
+		Some("ltr") => HtmlDirection::Ltr,
+		Some("rtl") => HtmlDirection::Rtl,
+		Some(other) => {
+			warn!("invalid `dir` `{other}`, expected `ltr` or `rtl`");
+			return;
+		}
+		None => return,
+	};
+	set_direction(entity, direction);
+}
+
+/// Gives the root of a document the direction of the [`HtmlDirection`] resource.
+pub(crate) fn insert_root_direction(entity: &mut EntityWorldMut) {
+	let direction = entity
+		.world()
+		.get_resource::<HtmlDirection>()
+		.copied()
+		.unwrap_or_default();
+	set_direction(entity, direction);
+	apply_direction(entity);
+}
+
+fn set_direction(entity: &mut EntityWorldMut, direction: HtmlDirection) {
+	if let Some(mut attributes) = entity.get_mut::<AttributeList>() {
+		attributes.set_attribute("dir", direction.as_str());
+		entity.insert(direction);
+	} else {
+		entity.insert((
+			direction,
+			AttributeList::from_iter([("dir", direction.as_str())]),
+		));
+	}
+}
+
+/// The direction of the closest of `entity` and its ancestors that has one, or else that of the
+/// [`HtmlDirection`] resource.
+fn direction_of(world: &World, entity: Entity) -> HtmlDirection {
+	let mut current = Some(entity);
+	while let Some(e) = current {
+		if let Some(direction) = world.get::<HtmlDirection>(e) {
+			return *direction;
+		}
+		current = world.get::<ChildOf>(e).map(ChildOf::parent);
+	}
+	world
+		.get_resource::<HtmlDirection>()
+		.copied()
+		.unwrap_or_default()
+}
+
+/// Marks an element whose layout is mirrored for right-to-left, so that it can be mirrored back if
+/// its direction changes.
+#[derive(Component)]
+pub(crate) struct HtmlMirrored {
+	/// Whether its text was right-justified.
+	text: bool,
+}
+
+/// Marks an element that follows the direction it is in, unlike the parts widgets spawn for it,
+/// such as the thumb of a `<slider>`, which they lay out themselves.
+#[derive(Component)]
+pub(crate) struct HtmlDirectional;
+
+/// Mirrors the `Node` and text of `entity` if it is right-to-left, once its children are spawned,
+/// and marks it to be mirrored back if its direction changes.
+pub(crate) fn apply_direction(entity: &mut EntityWorldMut) {
+	entity.insert(HtmlDirectional);
+	update_direction(entity);
+}
+
+/// Mirrors the `Node` and text of `entity` if it is right-to-left and not mirrored yet, or mirrors
+/// them back if it is mirrored and no longer right-to-left.
+fn update_direction(entity: &mut EntityWorldMut) {
+	let rtl = direction_of(entity.world(), entity.id()) == HtmlDirection::Rtl;
+	if rtl == entity.contains::<HtmlMirrored>() {
+		return;
+	}
+	let column_item = entity
+		.get::<ChildOf>()
+		.and_then(|child_of| entity.world().get::<Node>(child_of.parent()))
+		.is_some_and(is_column);
+	if let Some(mut node) = entity.get_mut::<Node>() {
+		mirror_node(&mut node, column_item);
+	}
+
+	if rtl {
+		let mut text = false;
+		if entity.contains::<Text>()
+			&& let Some(mut layout) = entity.get_mut::<TextLayout>()
+			&& layout.justify == Justify::Left
+		{
+			layout.justify = Justify::Right;
+			text = true;
+		}
+		entity.insert(HtmlMirrored { text });
+	} else if entity
+		.take::<HtmlMirrored>()
+		.is_some_and(|mirrored| mirrored.text)
+		&& let Some(mut layout) = entity.get_mut::<TextLayout>()
+		&& layout.justify == Justify::Right
+	{
+		layout.justify = Justify::Left;
+	}
+}
+
+/// Whether the items of `node` are laid out top to bottom, making their `align_self` horizontal.
+fn is_column(node: &Node) -> bool {
+	matches!(
+		node.flex_direction,
+		FlexDirection::Column | FlexDirection::ColumnReverse
+	)
+}
+
+/// Mirrors the horizontal layout of `node`, as if it was written for right-to-left. Its `align_self`
+/// is horizontal only if it is a `column_item`, within a column. Mirroring twice gives back the
+/// original, whatever its `display`, so that styles can be applied in between.
+fn mirror_node(node: &mut Node, column_item: bool) {
+	match node.flex_direction {
+		FlexDirection::Row | FlexDirection::RowReverse => {
+			// `flex-start` and `flex-end` follow the reversed row, while `start` and `end` do not.
+			node.flex_direction = if node.flex_direction == FlexDirection::Row {
+				FlexDirection::RowReverse
+			} else {
+				FlexDirection::Row
+			};
+			node.justify_content = match node.justify_content {
+				JustifyContent::Start => JustifyContent::End,
+				JustifyContent::End => JustifyContent::Start,
+				other => other,
+			};
+		}
+		FlexDirection::Column | FlexDirection::ColumnReverse => {
+			node.align_items = match node.align_items {
+				AlignItems::Start => AlignItems::End,
+				AlignItems::End => AlignItems::Start,
+				AlignItems::FlexStart => AlignItems::FlexEnd,
+				AlignItems::FlexEnd => AlignItems::FlexStart,
+				other => other,
+			};
+		}
+	}
+	node.justify_items = match node.justify_items {
+		JustifyItems::Start => JustifyItems::End,
+		JustifyItems::End => JustifyItems::Start,
+		other => other,
+	};
+	node.justify_self = match node.justify_self {
+		JustifySelf::Start => JustifySelf::End,
+		JustifySelf::End => JustifySelf::Start,
+		other => other,
+	};
+	if column_item {
+		node.align_self = match node.align_self {
+			AlignSelf::Start => AlignSelf::End,
+			AlignSelf::End => AlignSelf::Start,
+			AlignSelf::FlexStart => AlignSelf::FlexEnd,
+			AlignSelf::FlexEnd => AlignSelf::FlexStart,
+			other => other,
+		};
+	}
+
+	reverse_grid_tracks(&mut node.grid_template_columns);
+	node.grid_column = mirror_grid_placement(node.grid_column);
+
+	for rect in [&mut node.padding, &mut node.margin, &mut node.border] {
+		std::mem::swap(&mut rect.left, &mut rect.right);
+	}
+	std::mem::swap(&mut node.left, &mut node.right);
+}
+
+/// Reverses the order of `tracks`, including the tracks within each `repeat()`.
+fn reverse_grid_tracks(tracks: &mut [RepeatedGridTrack]) {
+	tracks.reverse();
+	for track in tracks {
+		// The tracks of a repeat are only reachable through reflection.
+		if let ReflectMut::Struct(track) = track.reflect_mut()
+			&& let Some(ReflectMut::List(repeated)) =
+				track.field_mut("tracks").map(PartialReflect::reflect_mut)
+			&& repeated.len() > 1
+		{
+			let mut items = repeated.drain();
+			items.reverse();
+			for item in items {
+				repeated.push(item);
+			}
+		}
+	}
+}
+
+/// Places a grid item on the columns that mirror `placement`, counting lines from the other end.
+fn mirror_grid_placement(placement: GridPlacement) -> GridPlacement {
+	let span = placement.get_span().unwrap_or(1);
+	match (placement.get_start(), placement.get_end()) {
+		(Some(start), Some(end)) => GridPlacement::start_end(-end, -start),
+		(Some(start), None) => GridPlacement::end_span(-start, span),
+		(None, Some(end)) => GridPlacement::start_span(-end, span),
+		(None, None) => placement,
+	}
+}
+
+/// The right-to-left elements mirrored back while `bevy_flair` applies their styles this frame, and
+/// whether each is a column item.
+#[derive(Resource, Default)]
+pub(crate) struct HtmlRestyled(Vec<(Entity, bool)>);
+
+/// Whether `bevy_flair` applies the styles of the entity of `marker` this frame.
+fn needs_styles(marker: &NodeStyleMarker) -> bool {
+	// The flag is only readable through reflection.
+	let ReflectRef::Struct(marker) = marker.reflect_ref() else {
+		return true;
+	};
+	marker
+		.field("needs_property_application")
+		.and_then(|needs| needs.try_downcast_ref::<bool>())
+		.is_none_or(|needs| *needs)
+}
+
+/// Mirrors back the right-to-left `Node`s whose styles `bevy_flair` is about to apply, so that
+/// stylesheets set left-to-right properties, which [`html_direction_mirror_styles`] then mirrors
+/// like the rest. Elements whose styles did not change are left alone.
+///
+/// Change detection is bypassed, so only the properties styles change mark a `Node` as changed.
+#[allow(clippy::type_complexity)]
+pub(crate) fn html_direction_unmirror_styles(
+	mut nodes: ParamSet<(
+		Query<(Entity, &NodeStyleMarker, Option<&ChildOf>), With<HtmlMirrored>>,
+		Query<&Node>,
+		Query<&mut Node>,
+	)>,
+	mut restyled: ResMut<HtmlRestyled>,
+) {
+	restyled.0.clear();
+	let styled: Vec<(Entity, Option<Entity>)> = nodes
+		.p0()
+		.iter()
+		.filter(|(_, marker, _)| needs_styles(marker))
+		.map(|(entity, _, child_of)| (entity, child_of.map(ChildOf::parent)))
+		.collect();
+	for (entity, parent) in styled {
+		let column_item = parent.is_some_and(|parent| nodes.p1().get(parent).is_ok_and(is_column));
+		if let Ok(mut node) = nodes.p2().get_mut(entity) {
+			mirror_node(node.bypass_change_detection(), column_item);
+			restyled.0.push((entity, column_item));
+		}
+	}
+}
+
+/// Mirrors the `Node`s [`html_direction_unmirror_styles`] mirrored back again, once `bevy_flair` has
+/// applied their styles.
+pub(crate) fn html_direction_mirror_styles(
+	mut nodes: Query<&mut Node>,
+	mut restyled: ResMut<HtmlRestyled>,
+) {
+	for (entity, column_item) in restyled.0.drain(..) {
+		if let Ok(mut node) = nodes.get_mut(entity) {
+			mirror_node(node.bypass_change_detection(), column_item);
+		}
+	}
+}
+
+/// Gives every document the direction of the [`HtmlDirection`] resource, mirroring its elements in
+/// place.
+pub(crate) fn html_direction_update(world: &mut World) {
+	let mut q_roots = world.query_filtered::<Entity, With<HtmlUiRoot>>();
+	let roots: Vec<Entity> = q_roots.iter(world).collect();
+	let direction = world
+		.get_resource::<HtmlDirection>()
+		.copied()
+		.unwrap_or_default();
+
+	for root in roots {
+		set_direction(&mut world.entity_mut(root), direction);
+		let mut stack = vec![root];
+		while let Some(entity) = stack.pop() {
+			let mut entity_mut = world.entity_mut(entity);
+			if entity_mut.contains::<HtmlDirectional>() {
+				update_direction(&mut entity_mut);
+			}
+			if let Some(children) = world.get::<Children>(entity) {
+				stack.extend(children.iter());
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod direction_tests {
+	use std::{path::Path, thread, time::Duration};
+
+	use bevy::{
+		asset::io::{
+			AssetSourceBuilder, AssetSourceId,
+			memory::{Dir, MemoryAssetReader},
+		},
+		prelude::*,
+		time::TimePlugin,
+	};
+	use bevy_flair::{
+		prelude::*,
+		style::{StyleSheet, StyleSystems},
+	};
+
+	use super::{
+		HtmlDirection, HtmlRestyled, html_direction_mirror_styles, html_direction_unmirror_styles,
+		html_direction_update, insert_root_direction, mirror_node,
+	};
+	use crate::{
+		asset::HtmlUiAsset,
+		build::{
+			HtmlUiRoot,
+			build_tests::{spawn_form, spawn_laid_out, x_range},
+			spawn_node,
+		},
+		parser::{parse_grid_tracks, parse_htmlish},
+		tags::{HtmlTagRegistry, register_builtin_tags},
+	};
+
+	#[test]
+	fn test_mirror_node() {
+		let mut row = Node {
+			justify_content: JustifyContent::Start,
+			padding: UiRect::new(Val::Px(1.0), Val::Px(2.0), Val::Px(3.0), Val::Px(4.0)),
+			left: Val::Px(10.0),
+			..default()
+		};
+		mirror_node(&mut row, false);
+		assert_eq!(row.flex_direction, FlexDirection::RowReverse);
+		assert_eq!(row.justify_content, JustifyContent::End);
+		assert_eq!(
+			row.padding,
+			UiRect::new(Val::Px(2.0), Val::Px(1.0), Val::Px(3.0), Val::Px(4.0))
+		);
+		assert_eq!(row.left, Val::Auto);
+		assert_eq!(row.right, Val::Px(10.0));
+
+		let mut column = Node {
+			flex_direction: FlexDirection::Column,
+			align_items: AlignItems::FlexStart,
+			justify_content: JustifyContent::Start,
+			..default()
+		};
+		mirror_node(&mut column, false);
+		assert_eq!(column.flex_direction, FlexDirection::Column);
+		assert_eq!(column.align_items, AlignItems::FlexEnd);
+		assert_eq!(column.justify_content, JustifyContent::Start);
+
+		let mut item = Node {
+			align_self: AlignSelf::FlexStart,
+			..default()
+		};
+		mirror_node(&mut item, false);
+		assert_eq!(item.align_self, AlignSelf::FlexStart);
+		mirror_node(&mut item, true);
+		assert_eq!(item.align_self, AlignSelf::FlexEnd);
+
+		let mut grid = Node {
+			display: Display::Grid,
+			grid_template_columns: parse_grid_tracks("100px repeat(2, 1fr 2fr)").unwrap(),
+			grid_column: GridPlacement::start_span(2, 2),
+			..default()
+		};
+		mirror_node(&mut grid, false);
+		assert_eq!(
+			grid.grid_template_columns,
+			parse_grid_tracks("repeat(2, 2fr 1fr) 100px").unwrap()
+		);
+		assert_eq!(grid.grid_column, GridPlacement::end_span(-2, 2));
+		mirror_node(&mut grid, false);
+		assert_eq!(
+			grid.grid_template_columns,
+			parse_grid_tracks("100px repeat(2, 1fr 2fr)").unwrap()
+		);
+		assert_eq!(grid.grid_column, GridPlacement::start_span(2, 2));
+	}
+
+	/// The first column of a right-to-left grid is on the right, and keeps its width.
+	#[test]
+	fn test_grid_direction() {
+		let (app, root) = spawn_laid_out(
+			r#"<grid dir="rtl" width="300px" columns="100px 1fr"><node column="1" height="10px"></node><node column="2" height="10px"></node></grid>"#,
+		);
+		let world = app.world();
+		let grid = world.get::<Children>(root).unwrap()[0];
+		let items = world.get::<Children>(grid).unwrap();
+		assert_eq!(x_range(world, items[0]), (200.0, 300.0));
+		assert_eq!(x_range(world, items[1]), (0.0, 200.0));
+	}
+
+	/// An item aligned to the start of a right-to-left column is on its right.
+	#[test]
+	fn test_align_self_direction() {
+		let (app, root) = spawn_laid_out(
+			r#"<vbox dir="rtl" width="300px"><label align-self="flex-start" width="100px">Hi</label></vbox>"#,
+		);
+		let world = app.world();
+		let vbox = world.get::<Children>(root).unwrap()[0];
+		let label = world.get::<Children>(vbox).unwrap()[0];
+		assert_eq!(x_range(world, label), (200.0, 300.0));
+	}
+
+	/// The text after the box of a checkbox goes into a run, which is right-justified too.
+	#[test]
+	fn test_text_run_direction() {
+		let (app, root) = spawn_form(r#"<vbox dir="rtl"><checkbox>Sound</checkbox></vbox>"#);
+		let world = app.world();
+		let vbox = world.get::<Children>(root).unwrap()[0];
+		let checkbox = world.get::<Children>(vbox).unwrap()[0];
+		let run = *world.get::<Children>(checkbox).unwrap().last().unwrap();
+		assert_eq!(world.get::<Text>(run).unwrap().0, "Sound");
+		assert_eq!(
+			world.get::<TextLayout>(run).unwrap().justify,
+			Justify::Right
+		);
+	}
+
+	/// Spawns a right-to-left document styled by `css` under a bevy_flair app and returns its first
+	/// element once styles are applied.
+	fn spawn_styled(css: &str, source: &str) -> (App, Entity) {
+		let dir = Dir::default();
+		dir.insert_asset_text(Path::new("ui.css"), css);
+		let mut app = App::new();
+		app.register_asset_source(
+			AssetSourceId::Default,
+			AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+		)
+		.add_plugins((
+			TaskPoolPlugin::default(),
+			TimePlugin,
+			AssetPlugin::default(),
+			WindowPlugin::default(),
+			FlairPlugin,
+		))
+		.init_resource::<HtmlTagRegistry>()
+		.insert_resource(HtmlDirection::Rtl)
+		.init_resource::<HtmlRestyled>()
+		.add_systems(
+			PostUpdate,
+			(
+				html_direction_unmirror_styles
+					.after(StyleSystems::SetAnimationValues)
+					.before(StyleSystems::ApplyComputedProperties),
+				html_direction_mirror_styles.after(StyleSystems::ApplyComputedProperties),
+			),
+		);
+		register_builtin_tags(&mut app);
+		app.finish();
+
+		let css = app
+			.world()
+			.resource::<AssetServer>()
+			.load::<StyleSheet>("ui.css");
+		let world = app.world_mut();
+		let root = world
+			.spawn((Node::default(), NodeStyleSheet::new(css.clone())))
+			.id();
+		insert_root_direction(&mut world.entity_mut(root));
+		for node in &parse_htmlish(&source.into()).unwrap() {
+			spawn_node(world, root, node);
+		}
+		for _ in 0..1000 {
+			if app.world().resource::<AssetServer>().is_loaded(&css) {
+				break;
+			}
+			app.update();
+			thread::sleep(Duration::from_millis(1));
+		}
+		app.update();
+		let element = app.world().get::<Children>(root).unwrap()[0];
+		(app, element)
+	}
+
+	/// Properties set by stylesheets are mirrored, and stay mirrored as styles are applied again.
+	#[test]
+	fn test_styled_direction() {
+		let (mut app, row) = spawn_styled(
+			".row { padding-left: 10px; justify-content: start; }\n.wide { padding-left: 20px; }",
+			r#"<hbox class="row"></hbox>"#,
+		);
+		for _ in 0..2 {
+			let node = app.world().get::<Node>(row).unwrap();
+			assert_eq!(node.flex_direction, FlexDirection::RowReverse);
+			assert_eq!(node.justify_content, JustifyContent::End);
+			assert_eq!(node.padding.left, Val::Px(0.0));
+			assert_eq!(node.padding.right, Val::Px(10.0));
+			app.update();
+		}
+
+		app.world_mut()
+			.get_mut::<ClassList>(row)
+			.unwrap()
+			.add("wide");
+		app.update();
+		let node = app.world().get::<Node>(row).unwrap();
+		assert_eq!(node.justify_content, JustifyContent::End);
+		assert_eq!(node.padding.left, Val::Px(0.0));
+		assert_eq!(node.padding.right, Val::Px(20.0));
+
+		let (app, row) = spawn_styled(
+			".row { padding-left: 10px; }\n[dir=\"rtl\"] .row { padding-left: 4px; }",
+			r#"<hbox class="row"></hbox>"#,
+		);
+		let node = app.world().get::<Node>(row).unwrap();
+		assert_eq!(node.padding.left, Val::Px(0.0));
+		assert_eq!(node.padding.right, Val::Px(4.0));
+	}
+
+	#[test]
+	fn test_direction_update() {
+		let mut app = App::new();
+		app.init_resource::<HtmlTagRegistry>()
+			.init_resource::<HtmlDirection>();
+		register_builtin_tags(&mut app);
+
+		let world = app.world_mut();
+		let root = world
+			.spawn((
+				Node::default(),
+				HtmlUiRoot {
+					id: AssetId::<HtmlUiAsset>::default(),
+				},
+			))
+			.id();
+		let ast = parse_htmlish(&r#"<hbox><label>Hi</label></hbox><hbox dir="ltr"></hbox>"#.into())
+			.unwrap();
+		for node in &ast {
+			spawn_node(world, root, node);
+		}
+		let children = world.get::<Children>(root).unwrap().to_vec();
+		let (row, ltr_row) = (children[0], children[1]);
+		let label = world.get::<Children>(row).unwrap()[0];
+
+		world.insert_resource(HtmlDirection::Rtl);
+		html_direction_update(world);
+		assert_eq!(
+			world.get::<Node>(row).unwrap().flex_direction,
+			FlexDirection::RowReverse
+		);
+		assert_eq!(
+			world.get::<TextLayout>(label).unwrap().justify,
+			Justify::Right
+		);
+		assert_eq!(
+			world.get::<Node>(ltr_row).unwrap().flex_direction,
+			FlexDirection::Row
+		);
+
+		world.insert_resource(HtmlDirection::Ltr);
+		html_direction_update(world);
+		assert_eq!(
+			world.get::<Node>(row).unwrap().flex_direction,
+			FlexDirection::Row
+		);
+		assert_eq!(
+			world.get::<TextLayout>(label).unwrap().justify,
+			Justify::Left
+		);
+		assert_eq!(world.get::<Children>(root).unwrap().len(), 2);
+	}
+
+	/// Switching a `<slider>` to right-to-left mirrors it, but not the parts it positions itself,
+	/// just as spawning it right-to-left does.
+	#[test]
+	fn test_slider_direction_update() {
+		let source = r#"<slider value="25"></slider>"#;
+		let (mut app, root) = spawn_laid_out(source);
+		app.init_resource::<HtmlDirection>();
+		let slider = app.world().get::<Children>(root).unwrap()[0];
+		let world = app.world_mut();
+		world.entity_mut(root).insert(HtmlUiRoot {
+			id: AssetId::<HtmlUiAsset>::default(),
+		});
+		world.insert_resource(HtmlDirection::Rtl);
+		html_direction_update(world);
+		let switched: Vec<Node> = descendants(world, slider)
+			.map(|part| world.get::<Node>(part).unwrap().clone())
+			.collect();
+
+		let (mut app, root) = spawn_laid_out(&format!(r#"<node dir="rtl">{source}</node>"#));
+		let world = app.world_mut();
+		let node = world.get::<Children>(root).unwrap()[0];
+		let slider = world.get::<Children>(node).unwrap()[0];
+		let spawned: Vec<Node> = descendants(world, slider)
+			.map(|part| world.get::<Node>(part).unwrap().clone())
+			.collect();
+		assert_eq!(switched, spawned);
+		for part in &switched[1..] {
+			assert!(part.left == Val::Auto || part.right == Val::Auto);
+		}
+	}
+
+	/// `entity` and its descendants, parents first.
+	fn descendants(world: &World, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+		let mut stack = vec![entity];
+		std::iter::from_fn(move || {
+			let entity = stack.pop()?;
+			if let Some(children) = world.get::<Children>(entity) {
+				stack.extend(children.iter().rev());
+			}
+			Some(entity)
+		})
+	}
+}
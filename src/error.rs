@@ -16,4 +16,10 @@ pub enum HtmlUiError {
 
 	#[error("asset not found")]
 	AssetNotFound,
+
+	#[error("failed to include `{0}`: {1}")]
+	IncludeError(String, String),
+
+	#[error("failed to load stylesheet `{0}`: {1}")]
+	StyleSheetError(String, String),
 }
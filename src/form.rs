@@ -0,0 +1,89 @@
+use bevy::{
+	ecs::system::SystemParam,
+	input::{
+		ButtonState,
+		keyboard::{Key, KeyboardInput},
+	},
+	prelude::*,
+};
+
+use crate::data::HtmlValue;
+
+/// Sent when the user changes the value of a form element, such as typing into an `<input>`.
+///
+/// Changing the value from code does not send this.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct HtmlValueChanged {
+	pub entity: Entity,
+	pub value: HtmlValue,
+}
+
+/// Sent when the user submits a form element, such as pressing Enter in an `<input>`.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct HtmlValueSubmitted {
+	pub entity: Entity,
+	pub value: HtmlValue,
+}
+
+/// Reads whether the user confirmed the focused element this frame, with Enter or Space on the
+/// keyboard or the South button (A on Xbox controllers) on any gamepad.
+#[derive(SystemParam)]
+pub(crate) struct ConfirmInput<'w, 's> {
+	keyboard: MessageReader<'w, 's, KeyboardInput>,
+	gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl ConfirmInput<'_, '_> {
+	pub fn just_confirmed(&mut self) -> bool {
+		let mut confirmed = false;
+		for event in self.keyboard.read() {
+			confirmed |= event.state == ButtonState::Pressed
+				&& !event.repeat
+				&& matches!(event.logical_key, Key::Enter | Key::Space);
+		}
+		confirmed
+			|| self
+				.gamepads
+				.iter()
+				.any(|gamepad| gamepad.just_pressed(GamepadButton::South))
+	}
+}
+
+/// Reads which way the user stepped this frame, with the arrow keys on the keyboard or the D-pad on
+/// any gamepad, as `-1`, `0` or `1` on each axis with `y` increasing downwards.
+#[derive(SystemParam)]
+pub(crate) struct StepInput<'w, 's> {
+	keyboard: MessageReader<'w, 's, KeyboardInput>,
+	gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl StepInput<'_, '_> {
+	pub fn just_stepped(&mut self) -> IVec2 {
+		let mut step = IVec2::ZERO;
+		for event in self.keyboard.read() {
+			if event.state != ButtonState::Pressed {
+				continue;
+			}
+			step += match event.logical_key {
+				Key::ArrowLeft => IVec2::NEG_X,
+				Key::ArrowRight => IVec2::X,
+				Key::ArrowUp => IVec2::NEG_Y,
+				Key::ArrowDown => IVec2::Y,
+				_ => IVec2::ZERO,
+			};
+		}
+		for gamepad in &self.gamepads {
+			for (button, direction) in [
+				(GamepadButton::DPadLeft, IVec2::NEG_X),
+				(GamepadButton::DPadRight, IVec2::X),
+				(GamepadButton::DPadUp, IVec2::NEG_Y),
+				(GamepadButton::DPadDown, IVec2::Y),
+			] {
+				if gamepad.just_pressed(button) {
+					step += direction;
+				}
+			}
+		}
+		step.clamp(IVec2::NEG_ONE, IVec2::ONE)
+	}
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use crate::{
+	ast::HtmlElement,
+	parser::{parse_grid_placement, parse_grid_tracks},
+	tags::HtmlTagHandler,
+};
+
+/// `<grid columns="repeat(4, 1fr)" rows="auto 1fr" flow="row dense">`, a CSS grid container.
+///
+/// Its children are placed with [`insert_grid_placement`].
+#[derive(Default)]
+pub(crate) struct GridTag;
+
+impl HtmlTagHandler for GridTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let tracks = |name: &str| {
+			element.attribute(name).map_or_else(Vec::new, |value| {
+				parse_grid_tracks(value).unwrap_or_else(|err| {
+					warn!("invalid <grid> {name}: {err}");
+					Vec::new()
+				})
+			})
+		};
+		let flow = match element.attribute("flow") {
+			None | Some("row") => GridAutoFlow::Row,
+			Some("column") => GridAutoFlow::Column,
+			Some("row dense") => GridAutoFlow::RowDense,
+			Some("column dense") => GridAutoFlow::ColumnDense,
+			Some(other) => {
+				warn!(
+					"unknown <grid> flow `{other}`, expected row, column, row dense or column dense"
+				);
+				GridAutoFlow::Row
+			}
+		};
+
+		entity.insert((
+			Node {
+				display: Display::Grid,
+				grid_template_columns: tracks("columns"),
+				grid_template_rows: tracks("rows"),
+				grid_auto_flow: flow,
+				row_gap: element.gap,
+				column_gap: element.gap,
+				..default()
+			},
+			Pickable::IGNORE,
+		));
+	}
+}
+
+/// Places an element within its parent `<grid>` from its `column` and `row` attributes, such as
+/// `column="2 / span 2"`. Works on any tag.
+pub(crate) fn insert_grid_placement(entity: &mut EntityWorldMut, element: &HtmlElement) {
+	for (name, vertical) in [("column", false), ("row", true)] {
+		let Some(value) = element.attribute(name) else {
+			continue;
+		};
+		let placement = match parse_grid_placement(value) {
+			Ok(placement) => placement,
+			Err(err) => {
+				warn!("invalid grid {name} on <{}>: {err}", element.tag.as_str());
+				continue;
+			}
+		};
+		if let Some(mut node) = entity.get_mut::<Node>() {
+			if vertical {
+				node.grid_row = placement;
+			} else {
+				node.grid_column = placement;
+			}
+		}
+	}
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use bevy::{input::keyboard::KeyboardInput, log::warn, prelude::*};
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::HtmlElement, build::load_attribute_asset, span::join_text_row, tags::HtmlTagHandler,
+};
+
+/// The height of `<icon>` and `<key>` glyphs, about that of a line of the default font. Stylesheets
+/// can size them like any other element.
+const GLYPH_HEIGHT: f32 = 20.0;
+
+/// The kind of device the player last pressed a button on, which `<key>` glyphs are shown for.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HtmlInputDevice {
+	#[default]
+	Keyboard,
+	Gamepad,
+}
+
+/// The images `<key action="confirm"/>` shows for each action, one for the keyboard and one for
+/// gamepads.
+///
+/// ```ignore
+/// app.insert_resource(
+///     HtmlKeyGlyphs::default()
+///         .with_glyph("confirm", asset_server.load("keys/enter.png"), asset_server.load("keys/a.png")),
+/// );
+/// ```
+#[derive(Resource, Default)]
+pub struct HtmlKeyGlyphs {
+	glyphs: HashMap<String, (Handle<Image>, Handle<Image>)>,
+}
+
+impl HtmlKeyGlyphs {
+	#[must_use]
+	pub fn with_glyph(
+		mut self,
+		action: impl Into<String>,
+		keyboard: Handle<Image>,
+		gamepad: Handle<Image>,
+	) -> Self {
+		self.insert(action, keyboard, gamepad);
+		self
+	}
+
+	pub fn insert(
+		&mut self,
+		action: impl Into<String>,
+		keyboard: Handle<Image>,
+		gamepad: Handle<Image>,
+	) {
+		self.glyphs.insert(action.into(), (keyboard, gamepad));
+	}
+
+	/// The glyph of `action` for `device`, if it has one.
+	#[must_use]
+	pub fn get(&self, action: &str, device: HtmlInputDevice) -> Option<&Handle<Image>> {
+		self.glyphs
+			.get(action)
+			.map(|(keyboard, gamepad)| match device {
+				HtmlInputDevice::Keyboard => keyboard,
+				HtmlInputDevice::Gamepad => gamepad,
+			})
+	}
+}
+
+/// A `<key>`, whose image follows [`HtmlKeyGlyphs`] and the [`HtmlInputDevice`].
+#[derive(Component, Clone, Debug)]
+pub struct HtmlKey {
+	pub action: String,
+}
+
+/// `<icon src="icons/coin.png"/>`, an image laid out within the text of its parent, as in
+/// `<label>Costs 5 <icon src="icons/coin.png"/></label>`.
+#[derive(Default)]
+pub(crate) struct IconTag;
+
+impl HtmlTagHandler for IconTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let Some(src) = element.attribute("src") else {
+			warn!("<icon> without a src");
+			return;
+		};
+		let Some(image) = load_attribute_asset(entity.world(), entity.id(), src) else {
+			warn!("<icon> with an empty src");
+			return;
+		};
+		insert_glyph(entity, ImageNode::new(image), "icon");
+	}
+}
+
+/// `<key action="confirm"/>`, the glyph of the button bound to `action` on the current input device,
+/// laid out within text like an `<icon>`.
+#[derive(Default)]
+pub(crate) struct KeyTag;
+
+impl HtmlTagHandler for KeyTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let Some(action) = element.attribute("action") else {
+			warn!("<key> without an action");
+			return;
+		};
+		entity.insert(HtmlKey {
+			action: action.to_owned(),
+		});
+		insert_glyph(entity, ImageNode::default(), "key");
+	}
+}
+
+fn insert_glyph(entity: &mut EntityWorldMut, image_node: ImageNode, type_name: &'static str) {
+	entity.insert((
+		Node {
+			height: Val::Px(GLYPH_HEIGHT),
+			..default()
+		},
+		image_node,
+		TypeName(type_name),
+		Pickable::IGNORE,
+	));
+	join_text_row(entity);
+}
+
+/// Switches the [`HtmlInputDevice`] to whichever kind of device had a button pressed.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_input_device_detect(
+	mut keyboard: MessageReader<KeyboardInput>,
+	gamepads: Query<&Gamepad>,
+	mut device: ResMut<HtmlInputDevice>,
+) {
+	let keyboard_pressed = keyboard.read().any(|input| input.state.is_pressed());
+	if gamepads
+		.iter()
+		.any(|gamepad| gamepad.get_just_pressed().next().is_some())
+	{
+		device.set_if_neq(HtmlInputDevice::Gamepad);
+	} else if keyboard_pressed {
+		device.set_if_neq(HtmlInputDevice::Keyboard);
+	}
+}
+
+/// Shows the glyph of each `<key>` for the current input device.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_key_update_glyphs(
+	device: Res<HtmlInputDevice>,
+	glyphs: Res<HtmlKeyGlyphs>,
+	mut q_keys: Query<(Ref<HtmlKey>, &mut ImageNode)>,
+) {
+	let changed = device.is_changed() || glyphs.is_changed();
+	for (key, mut image_node) in &mut q_keys {
+		if !changed && !key.is_changed() {
+			continue;
+		}
+		match glyphs.get(&key.action, *device) {
+			Some(image) => image_node.image = image.clone(),
+			None if key.is_added() => warn!("no glyph for <key action=\"{}\">", key.action),
+			None => {}
+		}
+	}
+}
+
+#[cfg(test)]
+mod icon_tests {
+	use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+	use super::{HtmlInputDevice, HtmlKey, HtmlKeyGlyphs, html_key_update_glyphs};
+
+	#[test]
+	fn test_key_glyphs() {
+		let mut world = World::new();
+		let mut images = Assets::<Image>::default();
+		let keyboard = images.add(Image::default());
+		let gamepad = images.add(Image::default());
+		world.init_resource::<HtmlInputDevice>();
+		world.insert_resource(HtmlKeyGlyphs::default().with_glyph(
+			"confirm",
+			keyboard.clone(),
+			gamepad.clone(),
+		));
+		let key = world
+			.spawn((
+				HtmlKey {
+					action: "confirm".to_owned(),
+				},
+				ImageNode::default(),
+			))
+			.id();
+
+		world.insert_resource(HtmlInputDevice::Gamepad);
+		world.run_system_once(html_key_update_glyphs).unwrap();
+		assert_eq!(world.get::<ImageNode>(key).unwrap().image, gamepad);
+
+		world.insert_resource(HtmlInputDevice::Keyboard);
+		world.run_system_once(html_key_update_glyphs).unwrap();
+		assert_eq!(world.get::<ImageNode>(key).unwrap().image, keyboard);
+	}
+}
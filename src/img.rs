@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use bevy::{log::warn, prelude::*};
+
+use crate::{ast::HtmlElement, build::load_attribute_asset, tags::HtmlTagHandler};
+
+/// `<img src="icons/sword.png" />`, spawned as an [`ImageNode`].
+///
+/// Supports `flip="x|y|xy"`, `mode="auto|stretch|sliced|tiled"` (see [`image_mode`]), and texture
+/// atlases through `atlas="4x2"`, `atlas-tile="16x16"` and `atlas-index="3"`.
+#[derive(Default)]
+pub(crate) struct ImgTag;
+
+impl HtmlTagHandler for ImgTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let Some(src) = element.attribute("src") else {
+			warn!("<img> without a src");
+			return;
+		};
+
+		let Some(image) = load_attribute_asset(entity.world(), entity.id(), src) else {
+			warn!("<img> with an empty src");
+			return;
+		};
+		let mut image_node = ImageNode::new(image);
+
+		if let Some(flip) = element.attribute("flip") {
+			image_node.flip_x = flip.contains('x');
+			image_node.flip_y = flip.contains('y');
+		}
+
+		image_node.image_mode = image_mode(element, NodeImageMode::Auto);
+
+		if let Some(atlas) = element.attribute("atlas") {
+			let grid = parse_size(atlas);
+			let tile = element.attribute("atlas-tile").and_then(parse_size);
+			let index = element
+				.attribute("atlas-index")
+				.map_or(Some(0), |index| index.trim().parse::<usize>().ok());
+			if let (Some(grid), Some(tile), Some(index)) = (grid, tile, index) {
+				let layout = entity.world_scope(|world| atlas_layout(world, grid, tile));
+				image_node.texture_atlas = Some(TextureAtlas { layout, index });
+			} else {
+				warn!(
+					"<img> atlas needs `atlas=\"COLUMNSxROWS\"`, `atlas-tile=\"WIDTHxHEIGHT\"` and a numeric `atlas-index`"
+				);
+			}
+		}
+
+		entity.insert((image_node, Pickable::IGNORE));
+	}
+}
+
+/// The atlas layouts of `<img atlas=...>`, shared by every image with the same grid and tile size.
+#[derive(Resource, Default)]
+struct HtmlAtlasLayouts(HashMap<(UVec2, UVec2), Handle<TextureAtlasLayout>>);
+
+fn atlas_layout(world: &mut World, grid: UVec2, tile: UVec2) -> Handle<TextureAtlasLayout> {
+	if let Some(layout) = world
+		.get_resource_or_init::<HtmlAtlasLayouts>()
+		.0
+		.get(&(grid, tile))
+	{
+		return layout.clone();
+	}
+	let layout =
+		world
+			.resource_mut::<Assets<TextureAtlasLayout>>()
+			.add(TextureAtlasLayout::from_grid(
+				tile, grid.x, grid.y, None, None,
+			));
+	world
+		.resource_mut::<HtmlAtlasLayouts>()
+		.0
+		.insert((grid, tile), layout.clone());
+	layout
+}
+
+/// `<panel image="ui/frame.png" slice="12px">`, a vertical box meant to be drawn with a nine-slice
+/// background. The background itself works on any tag through [`insert_background_image`].
+#[derive(Default)]
+pub(crate) struct PanelTag;
+
+impl HtmlTagHandler for PanelTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		entity.insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: FlexDirection::Column,
+				row_gap: element.gap,
+				..default()
+			},
+			Pickable {
+				is_hoverable: false,
+				should_block_lower: true,
+			},
+		));
+	}
+}
+
+/// Draws the `image` attribute of any tag behind its children, sliced if it has a `slice` attribute
+/// and stretched otherwise.
+pub(crate) fn insert_background_image(entity: &mut EntityWorldMut, element: &HtmlElement) {
+	let Some(image) = element
+		.attribute("image")
+		.and_then(|path| load_attribute_asset(entity.world(), entity.id(), path))
+	else {
+		return;
+	};
+	let default_mode = if element.attribute("slice").is_some() {
+		NodeImageMode::Sliced(texture_slicer(element))
+	} else {
+		NodeImageMode::Stretch
+	};
+	entity.insert(ImageNode::new(image).with_mode(image_mode(element, default_mode)));
+}
+
+/// Reads `mode` into the way an image fills its node, or `default` without one.
+///
+/// `mode="sliced"` reads its slicer from [`texture_slicer`], and `mode="tiled"` reads
+/// `tile-stretch="1.0"`.
+pub(crate) fn image_mode(element: &HtmlElement, default: NodeImageMode) -> NodeImageMode {
+	match element.attribute("mode").map(str::trim) {
+		None => default,
+		Some("auto") => NodeImageMode::Auto,
+		Some("stretch") => NodeImageMode::Stretch,
+		Some("sliced") => NodeImageMode::Sliced(texture_slicer(element)),
+		Some("tiled") => NodeImageMode::Tiled {
+			tile_x: true,
+			tile_y: true,
+			stretch_value: element
+				.attribute("tile-stretch")
+				.and_then(|value| value.trim().parse().ok())
+				.unwrap_or(1.0),
+		},
+		Some(mode) => {
+			warn!("unknown image mode `{mode}`");
+			default
+		}
+	}
+}
+
+/// Reads a nine-slice configuration from `slice="12px"` (with one to four insets in CSS order),
+/// `slice-center="stretch|tile"`, `slice-sides="stretch|tile"` and `slice-max-corner="1.0"`.
+///
+/// The scale modes accept a stretch value for tiling, such as `slice-center="tile 0.5"`.
+pub(crate) fn texture_slicer(element: &HtmlElement) -> TextureSlicer {
+	let border = element
+		.attribute("slice")
+		.map_or(Some(BorderRect::ZERO), parse_insets)
+		.unwrap_or_else(|| {
+			warn!("invalid `slice` insets, expected one to four lengths such as `12px 8px`");
+			BorderRect::ZERO
+		});
+
+	TextureSlicer {
+		border,
+		center_scale_mode: slice_scale_mode(element, "slice-center"),
+		sides_scale_mode: slice_scale_mode(element, "slice-sides"),
+		max_corner_scale: element
+			.attribute("slice-max-corner")
+			.and_then(|value| value.trim().parse().ok())
+			.unwrap_or(1.0),
+	}
+}
+
+fn slice_scale_mode(element: &HtmlElement, name: &str) -> SliceScaleMode {
+	let Some(value) = element.attribute(name) else {
+		return SliceScaleMode::Stretch;
+	};
+	let mut words = value.split_whitespace();
+	match (words.next(), words.next().map(str::parse::<f32>)) {
+		(Some("stretch"), None) => SliceScaleMode::Stretch,
+		(Some("tile"), None) => SliceScaleMode::Tile { stretch_value: 1.0 },
+		(Some("tile"), Some(Ok(stretch_value))) => SliceScaleMode::Tile { stretch_value },
+		_ => {
+			warn!("invalid `{name}` value `{value}`, expected `stretch` or `tile`");
+			SliceScaleMode::Stretch
+		}
+	}
+}
+
+/// Parses one to four pixel lengths into insets, ordered like CSS `border-width`: all sides,
+/// vertical then horizontal, top then horizontal then bottom, or top, right, bottom, left.
+fn parse_insets(value: &str) -> Option<BorderRect> {
+	let values = value
+		.split_whitespace()
+		.map(parse_px)
+		.collect::<Option<Vec<_>>>()?;
+	let (top, right, bottom, left) = match values[..] {
+		[all] => (all, all, all, all),
+		[vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+		[top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+		[top, right, bottom, left] => (top, right, bottom, left),
+		_ => return None,
+	};
+	Some(BorderRect {
+		min_inset: Vec2::new(left, top),
+		max_inset: Vec2::new(right, bottom),
+	})
+}
+
+/// Parses a pixel length such as `12px` or `12`.
+pub(crate) fn parse_px(value: &str) -> Option<f32> {
+	let value = value.trim();
+	value
+		.strip_suffix("px")
+		.unwrap_or(value)
+		.trim()
+		.parse()
+		.ok()
+}
+
+/// Parses a size such as `16x16`.
+fn parse_size(value: &str) -> Option<UVec2> {
+	let (x, y) = value.trim().split_once('x')?;
+	Some(UVec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod img_tests {
+	use bevy::prelude::*;
+
+	use crate::{
+		asset::HtmlUiAsset,
+		ast::HtmlNode,
+		build::spawn_html_ui,
+		loader::loader_tests::{document_app, wait_for_load},
+		parser::parse_htmlish,
+		resources::HtmlCssUiResource,
+	};
+
+	use super::{HtmlAtlasLayouts, texture_slicer};
+
+	/// `<img>` loads its `src` relative to the document, and images with the same atlas grid share
+	/// one layout.
+	#[test]
+	fn test_img() {
+		let files = [(
+			"ui/menu.html",
+			r#"<ui>
+				<img src="icons/sword.png" flip="x" mode="stretch" atlas="4x2" atlas-tile="16x16" atlas-index="3" />
+				<img src="../logo.png" atlas="4x2" atlas-tile="16x16" />
+			</ui>"#,
+		)];
+		let mut app = document_app(&files);
+		app.init_asset::<TextureAtlasLayout>();
+		let handle = app
+			.world()
+			.resource::<AssetServer>()
+			.load::<HtmlUiAsset>("ui/menu.html");
+		assert!(wait_for_load(&mut app, &handle));
+		app.insert_resource(HtmlCssUiResource::new(handle.clone(), None));
+		spawn_html_ui(app.world_mut(), handle.id()).unwrap();
+
+		let world = app.world_mut();
+		let mut q_images = world.query::<&ImageNode>();
+		let images: Vec<ImageNode> = q_images.iter(world).cloned().collect();
+		let [sword, logo] = &images[..] else {
+			panic!("expected two images, got {}", images.len());
+		};
+		let path = |image: &ImageNode| image.image.path().unwrap().to_string();
+		assert_eq!(path(sword), "ui/icons/sword.png");
+		assert_eq!(path(logo), "logo.png");
+
+		assert!(sword.flip_x && !sword.flip_y);
+		assert_eq!(sword.image_mode, NodeImageMode::Stretch);
+		assert!(!logo.flip_x);
+		assert_eq!(logo.image_mode, NodeImageMode::Auto);
+
+		let sword_atlas = sword.texture_atlas.as_ref().unwrap();
+		let logo_atlas = logo.texture_atlas.as_ref().unwrap();
+		assert_eq!((sword_atlas.index, logo_atlas.index), (3, 0));
+		assert_eq!(sword_atlas.layout, logo_atlas.layout);
+		assert_eq!(world.resource::<HtmlAtlasLayouts>().0.len(), 1);
+		let layout = world
+			.resource::<Assets<TextureAtlasLayout>>()
+			.get(&sword_atlas.layout)
+			.unwrap();
+		assert_eq!(layout.len(), 8);
+		assert_eq!(layout.size, UVec2::new(64, 32));
+	}
+
+	#[test]
+	fn test_texture_slicer() {
+		let ast = parse_htmlish(
+			&r#"<panel image="ui/frame.png" slice="12px 8px 4px 2px" slice-center="tile 0.5" slice-max-corner="2"></panel>"#
+				.into(),
+		)
+		.unwrap();
+		let HtmlNode::Element(element) = &ast[0] else {
+			panic!("<panel> is not Element");
+		};
+		let slicer = texture_slicer(element);
+		assert_eq!(slicer.border.min_inset, Vec2::new(2.0, 12.0));
+		assert_eq!(slicer.border.max_inset, Vec2::new(8.0, 4.0));
+		assert_eq!(
+			slicer.center_scale_mode,
+			SliceScaleMode::Tile { stretch_value: 0.5 }
+		);
+		assert_eq!(slicer.sides_scale_mode, SliceScaleMode::Stretch);
+		assert!((slicer.max_corner_scale - 2.0).abs() < f32::EPSILON);
+	}
+}
@@ -0,0 +1,760 @@
+use std::ops::Range;
+
+use bevy::{
+	input::{
+		ButtonState,
+		keyboard::{Key, KeyboardInput},
+	},
+	input_focus::InputFocus,
+	log::warn,
+	prelude::*,
+	text::ComputedTextBlock,
+	ui::{UiGlobalTransform, auto_directional_navigation::AutoDirectionalNavigation},
+};
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::{HtmlElement, HtmlNode},
+	data::HtmlValue,
+	form::{HtmlValueChanged, HtmlValueSubmitted},
+	scroll::scroll_into_view,
+	tags::HtmlTagHandler,
+	text_edit::{TextEdit, caret_rect, hit_index, line_above_or_below, selection_rects},
+};
+
+/// Where `<input>` copies to and pastes from.
+///
+/// The default keeps copied text within the app. Implement this over the system clipboard, for
+/// example with `arboard`, and insert it as [`HtmlUiClipboard`] to share text with other programs.
+pub trait HtmlClipboard: Send + Sync + 'static {
+	fn read(&mut self) -> Option<String>;
+	fn write(&mut self, text: &str);
+}
+
+/// The clipboard used by every `<input>`.
+#[derive(Resource)]
+pub struct HtmlUiClipboard(pub Box<dyn HtmlClipboard>);
+
+impl HtmlUiClipboard {
+	pub fn new(clipboard: impl HtmlClipboard) -> Self {
+		Self(Box::new(clipboard))
+	}
+}
+
+impl Default for HtmlUiClipboard {
+	fn default() -> Self {
+		Self::new(MemoryClipboard::default())
+	}
+}
+
+#[derive(Default)]
+struct MemoryClipboard(Option<String>);
+
+impl HtmlClipboard for MemoryClipboard {
+	fn read(&mut self) -> Option<String> {
+		self.0.clone()
+	}
+
+	fn write(&mut self, text: &str) {
+		self.0 = Some(text.to_owned());
+	}
+}
+
+/// The value of an `<input type="text">` or `<textarea>`.
+#[derive(Component, Debug, Clone)]
+pub struct HtmlTextInput {
+	pub(crate) edit: TextEdit,
+	/// The most chars the user can enter, from the `maxlength` attribute.
+	pub max_length: Option<usize>,
+	pub(crate) multiline: bool,
+}
+
+impl HtmlTextInput {
+	#[must_use]
+	pub fn value(&self) -> &str {
+		&self.edit.value
+	}
+
+	/// Replaces the value, cut short to `max_length`, and puts the caret at its end.
+	///
+	/// Line breaks are replaced with spaces unless this is a `<textarea>`.
+	pub fn set_value(&mut self, value: impl Into<String>) {
+		let mut value = value.into();
+		if !self.multiline {
+			value = single_line(&value);
+		}
+		if let Some(max_length) = self.max_length
+			&& let Some((end, _)) = value.char_indices().nth(max_length)
+		{
+			value.truncate(end);
+		}
+		self.edit.set_value(value);
+	}
+
+	/// The selected byte range of the value, which is empty at the caret if nothing is selected.
+	#[must_use]
+	pub fn selection(&self) -> Range<usize> {
+		self.edit.selection()
+	}
+}
+
+/// The entities an `<input>` is drawn with, all inside `content`, which scrolls to keep the caret
+/// in view.
+#[derive(Component)]
+pub(crate) struct HtmlTextInputParts {
+	content: Entity,
+	text: Entity,
+	placeholder: Entity,
+	caret: Entity,
+	selections: Vec<Entity>,
+	/// How many lines of text a `<textarea>` is tall, from the `rows` attribute.
+	rows: Option<u16>,
+	/// Where the caret was last scrolled into view, so that the mouse wheel can scroll a
+	/// `<textarea>` away from it until it moves.
+	shown_caret: Option<Rect>,
+}
+
+/// Replaces the placeholder of `entity`, if it is an `<input>` or `<textarea>`.
+pub(crate) fn set_input_placeholder(world: &mut World, entity: Entity, placeholder: &str) {
+	let Some(parts) = world.get::<HtmlTextInputParts>(entity) else {
+		return;
+	};
+	if let Some(mut text) = world.get_mut::<Text>(parts.placeholder) {
+		placeholder.clone_into(&mut text.0);
+	}
+}
+
+/// `<input type="text" placeholder="Name" maxlength="16" value="">`, a single-line text field.
+///
+/// The field takes keyboard input while it has [`InputFocus`], which it gets when clicked or
+/// navigated to. It is drawn with the classes `input-text`, `input-placeholder`, `input-caret` and
+/// `input-selection` for styling.
+#[derive(Default)]
+pub(crate) struct InputTag;
+
+impl HtmlTagHandler for InputTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let kind = element.attribute("type").unwrap_or("text");
+		if kind != "text" {
+			warn!("unsupported <input> type `{kind}`, using `text`");
+		}
+		insert_text_field(entity, element, false);
+	}
+}
+
+/// `<textarea rows="4" placeholder="Notes">Initial text</textarea>`, a multi-line text field that
+/// wraps its lines and scrolls vertically.
+///
+/// Works like [`InputTag`], except that Enter starts a new line and Ctrl+Enter submits.
+#[derive(Default)]
+pub(crate) struct TextareaTag;
+
+impl HtmlTagHandler for TextareaTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		insert_text_field(entity, element, true);
+	}
+
+	fn spawn_children(&self) -> bool {
+		false
+	}
+}
+
+#[allow(clippy::too_many_lines)]
+fn insert_text_field(entity: &mut EntityWorldMut, element: &HtmlElement, multiline: bool) {
+	let mut input = HtmlTextInput {
+		edit: TextEdit::default(),
+		max_length: element
+			.attribute("maxlength")
+			.and_then(|value| value.trim().parse().ok()),
+		multiline,
+	};
+	let value = match element.attribute("value") {
+		Some(value) => value.to_owned(),
+		// A `<textarea>` holds its initial value as text, like in HTML.
+		None => element
+			.children
+			.iter()
+			.filter_map(|child| match child {
+				HtmlNode::Text(text) => Some(text.as_str()),
+				HtmlNode::Element(_) => None,
+			})
+			.collect(),
+	};
+	input.set_value(value);
+	let placeholder = element.attribute("placeholder").unwrap_or_default();
+	let rows = element
+		.attribute("rows")
+		.map(|rows| rows.trim().parse().unwrap_or(2));
+
+	let layout = if multiline {
+		TextLayout::default()
+	} else {
+		TextLayout::new_with_no_wrap()
+	};
+
+	let parts = entity.world_scope(|world| {
+		let text = world
+			.spawn((
+				Text::new(input.value()),
+				layout,
+				ClassList::new("input-text"),
+				Pickable::IGNORE,
+			))
+			.id();
+		let placeholder = world
+			.spawn((
+				Node {
+					position_type: PositionType::Absolute,
+					..default()
+				},
+				Text::new(placeholder),
+				layout,
+				TextColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
+				ClassList::new("input-placeholder"),
+				Pickable::IGNORE,
+			))
+			.id();
+		let caret = world
+			.spawn((
+				Node {
+					position_type: PositionType::Absolute,
+					width: Val::Px(1.0),
+					..default()
+				},
+				BackgroundColor(Color::WHITE),
+				Visibility::Hidden,
+				ClassList::new("input-caret"),
+				Pickable::IGNORE,
+			))
+			.id();
+		let content_node = if multiline {
+			Node {
+				flex_direction: FlexDirection::Column,
+				flex_shrink: 0.0,
+				width: Val::Percent(100.0),
+				..default()
+			}
+		} else {
+			Node {
+				flex_shrink: 0.0,
+				min_width: Val::Percent(100.0),
+				..default()
+			}
+		};
+		let content = world
+			.spawn((content_node, Pickable::IGNORE))
+			.add_children(&[text, placeholder, caret])
+			.id();
+		HtmlTextInputParts {
+			content,
+			text,
+			placeholder,
+			caret,
+			selections: Vec::new(),
+			rows,
+			shown_caret: None,
+		}
+	});
+
+	entity.add_child(parts.content).insert((
+		Node {
+			overflow: if multiline {
+				Overflow::scroll_y()
+			} else {
+				Overflow::scroll_x()
+			},
+			..default()
+		},
+		ScrollPosition::default(),
+		input,
+		parts,
+		AutoDirectionalNavigation::default(),
+		Pickable {
+			is_hoverable: true,
+			should_block_lower: true,
+		},
+	));
+}
+
+fn single_line(text: &str) -> String {
+	text.replace("\r\n", " ").replace(['\r', '\n'], " ")
+}
+
+/// Edits the focused `<input>` from the keyboard.
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_lines)]
+pub(crate) fn html_input_keyboard(
+	mut keyboard: MessageReader<KeyboardInput>,
+	keys: Option<Res<ButtonInput<KeyCode>>>,
+	mut focus: ResMut<InputFocus>,
+	mut inputs: Query<(&mut HtmlTextInput, &HtmlTextInputParts)>,
+	blocks: Query<&ComputedTextBlock>,
+	mut clipboard: ResMut<HtmlUiClipboard>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+	mut submitted: MessageWriter<HtmlValueSubmitted>,
+) {
+	let Some((entity, (mut input, parts))) = focus
+		.get()
+		.and_then(|entity| Some((entity, inputs.get_mut(entity).ok()?)))
+	else {
+		keyboard.clear();
+		return;
+	};
+
+	let pressed = |codes: [KeyCode; 2]| keys.as_ref().is_some_and(|keys| keys.any_pressed(codes));
+	let shift = pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+	// AltGr arrives as Ctrl+Alt on Windows, so Ctrl only makes a shortcut without Alt.
+	let command = (pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+		&& !pressed([KeyCode::AltLeft, KeyCode::AltRight]))
+		|| pressed([KeyCode::SuperLeft, KeyCode::SuperRight]);
+
+	let block = blocks.get(parts.text).ok();
+	let max_length = input.max_length;
+	let multiline = input.multiline;
+	let edit = &mut input.edit;
+	for event in keyboard.read() {
+		if event.state != ButtonState::Pressed {
+			continue;
+		}
+
+		let edited = match &event.logical_key {
+			Key::ArrowLeft => {
+				edit.move_left(command, shift);
+				false
+			}
+			Key::ArrowRight => {
+				edit.move_right(command, shift);
+				false
+			}
+			Key::ArrowUp | Key::ArrowDown => {
+				let down = event.logical_key == Key::ArrowDown;
+				let index = if multiline {
+					block.and_then(|block| {
+						line_above_or_below(block, &edit.value, edit.cursor, down)
+					})
+				} else {
+					None
+				};
+				let end = if down { edit.value.len() } else { 0 };
+				edit.move_to(index.unwrap_or(end), shift);
+				false
+			}
+			Key::Home => {
+				edit.move_to(edit.line_start(edit.cursor), shift);
+				false
+			}
+			Key::End => {
+				edit.move_to(edit.line_end(edit.cursor), shift);
+				false
+			}
+			Key::Backspace => edit.delete_backward(command),
+			Key::Delete => edit.delete_forward(command),
+			Key::Enter if multiline && !command => edit.insert("\n", max_length),
+			Key::Enter => {
+				submitted.write(HtmlValueSubmitted {
+					entity,
+					value: HtmlValue::String(edit.value.clone()),
+				});
+				false
+			}
+			Key::Escape => {
+				focus.clear();
+				break;
+			}
+			_ if command => match event.key_code {
+				KeyCode::KeyA => {
+					edit.select_all();
+					false
+				}
+				KeyCode::KeyC => {
+					if !edit.selection().is_empty() {
+						clipboard.0.write(edit.selected_text());
+					}
+					false
+				}
+				KeyCode::KeyX => {
+					if !edit.selection().is_empty() {
+						clipboard.0.write(edit.selected_text());
+					}
+					edit.insert("", None)
+				}
+				KeyCode::KeyV => clipboard.0.read().is_some_and(|text| {
+					let text = if multiline {
+						text.replace("\r\n", "\n").replace('\r', "\n")
+					} else {
+						single_line(&text)
+					};
+					edit.insert(&text, max_length)
+				}),
+				_ => false,
+			},
+			_ => match &event.text {
+				Some(text) if !text.chars().any(char::is_control) => edit.insert(text, max_length),
+				_ => false,
+			},
+		};
+
+		if edited {
+			changed.write(HtmlValueChanged {
+				entity,
+				value: HtmlValue::String(edit.value.clone()),
+			});
+		}
+	}
+}
+
+/// Focuses an `<input>` when it is clicked, and moves its caret to the click.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_input_press(
+	press: On<Pointer<Press>>,
+	keys: Option<Res<ButtonInput<KeyCode>>>,
+	mut focus: ResMut<InputFocus>,
+	mut inputs: Query<(&mut HtmlTextInput, &HtmlTextInputParts)>,
+	texts: Query<(&ComputedTextBlock, &ComputedNode, &UiGlobalTransform)>,
+) {
+	let Ok((mut input, parts)) = inputs.get_mut(press.entity) else {
+		return;
+	};
+	focus.set(press.entity);
+
+	let shift = keys
+		.as_ref()
+		.is_some_and(|keys| keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]));
+	if let Some(index) = pointer_index(
+		&texts,
+		parts.text,
+		input.value(),
+		press.pointer_location.position,
+	) {
+		input.edit.move_to(index, shift);
+	}
+}
+
+/// Selects text in an `<input>` by dragging over it.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_input_drag(
+	drag: On<Pointer<Drag>>,
+	mut inputs: Query<(&mut HtmlTextInput, &HtmlTextInputParts)>,
+	texts: Query<(&ComputedTextBlock, &ComputedNode, &UiGlobalTransform)>,
+) {
+	let Ok((mut input, parts)) = inputs.get_mut(drag.entity) else {
+		return;
+	};
+	if let Some(index) = pointer_index(
+		&texts,
+		parts.text,
+		input.value(),
+		drag.pointer_location.position,
+	) {
+		input.edit.move_to(index, true);
+	}
+}
+
+/// The byte index of `value` under the pointer at the logical window `position`.
+fn pointer_index(
+	texts: &Query<(&ComputedTextBlock, &ComputedNode, &UiGlobalTransform)>,
+	text: Entity,
+	value: &str,
+	position: Vec2,
+) -> Option<usize> {
+	let (block, node, transform) = texts.get(text).ok()?;
+	let physical = position / node.inverse_scale_factor();
+	let local = transform.try_inverse()?.transform_point2(physical) + node.size / 2.0;
+	hit_index(block, value, local)
+}
+
+/// Shows each `<input>`'s value or placeholder, moves its caret and selection to match the laid out
+/// text, and sizes each `<textarea>` to its `rows`.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_input_update_display(
+	mut commands: Commands,
+	focus: Res<InputFocus>,
+	mut inputs: Query<(
+		Entity,
+		&HtmlTextInput,
+		&mut HtmlTextInputParts,
+		&ComputedNode,
+		&mut ScrollPosition,
+	)>,
+	mut texts: Query<(&mut Text, &ComputedTextBlock, &ComputedNode, &TextFont)>,
+	mut nodes: Query<(&mut Node, &mut Visibility)>,
+) {
+	for (entity, input, mut parts, input_node, mut scroll) in &mut inputs {
+		let focused = focus.get() == Some(entity);
+		let value = input.value();
+
+		if let Ok((_, mut visibility)) = nodes.get_mut(parts.placeholder) {
+			visibility.set_if_neq(if value.is_empty() {
+				Visibility::Inherited
+			} else {
+				Visibility::Hidden
+			});
+		}
+
+		let Ok((mut text, block, text_node, font)) = texts.get_mut(parts.text) else {
+			continue;
+		};
+		if text.0 != value {
+			value.clone_into(&mut text.0);
+		}
+
+		let scale = text_node.inverse_scale_factor();
+		let to_logical = |rect: Rect| Rect::from_corners(rect.min * scale, rect.max * scale);
+		let caret = caret_rect(block, value, input.edit.cursor).map_or_else(
+			|| Rect::new(0.0, 0.0, 0.0, font.font_size * 1.2),
+			to_logical,
+		);
+		let selections: Vec<Rect> = if focused {
+			selection_rects(block, value, &input.selection())
+				.into_iter()
+				.map(to_logical)
+				.collect()
+		} else {
+			Vec::new()
+		};
+
+		if let Ok((mut node, mut visibility)) = nodes.get_mut(parts.caret) {
+			place(&mut node, caret);
+			visibility.set_if_neq(if focused {
+				Visibility::Inherited
+			} else {
+				Visibility::Hidden
+			});
+		}
+
+		while parts.selections.len() < selections.len() {
+			let selection = commands
+				.spawn((
+					Node {
+						position_type: PositionType::Absolute,
+						..default()
+					},
+					BackgroundColor(Color::srgba(0.3, 0.5, 1.0, 0.5)),
+					ClassList::new("input-selection"),
+					Pickable::IGNORE,
+				))
+				.id();
+			commands
+				.entity(parts.content)
+				.insert_children(0, &[selection]);
+			parts.selections.push(selection);
+		}
+		for selection in parts.selections.drain(selections.len()..) {
+			commands.entity(selection).despawn();
+		}
+		for (selection, rect) in parts.selections.iter().zip(selections) {
+			if let Ok((mut node, _)) = nodes.get_mut(*selection) {
+				place(&mut node, rect);
+			}
+		}
+
+		// Scroll just enough to keep the caret inside the content box whenever it moves.
+		let inset = input_node.content_inset();
+		if parts.shown_caret != Some(caret) {
+			parts.shown_caret = Some(caret);
+			let visible = (input_node.size - inset.min_inset - inset.max_inset)
+				* input_node.inverse_scale_factor();
+			let offset = Vec2::new(
+				scroll_into_view(scroll.x, caret.min.x, caret.max.x + 1.0, visible.x),
+				scroll_into_view(scroll.y, caret.min.y, caret.max.y, visible.y),
+			);
+			if scroll.0 != offset {
+				scroll.0 = offset;
+			}
+		}
+
+		if let Some(rows) = parts.rows
+			&& let Ok((mut node, _)) = nodes.get_mut(entity)
+		{
+			let insets =
+				(inset.min_inset.y + inset.max_inset.y) * input_node.inverse_scale_factor();
+			let height = Val::Px(f32::from(rows) * caret.height() + insets);
+			if node.height != height {
+				node.height = height;
+			}
+		}
+	}
+}
+
+/// Moves an absolutely positioned `node` to `rect`, keeping its styled width if `rect` has none.
+fn place(node: &mut Mut<Node>, rect: Rect) {
+	let left = Val::Px(rect.min.x);
+	let top = Val::Px(rect.min.y);
+	let height = Val::Px(rect.height());
+	let width = if rect.width() > 0.0 {
+		Val::Px(rect.width())
+	} else {
+		node.width
+	};
+	if node.left != left || node.top != top || node.width != width || node.height != height {
+		node.left = left;
+		node.top = top;
+		node.width = width;
+		node.height = height;
+	}
+}
+
+#[cfg(test)]
+mod input_tests {
+	use bevy::{
+		input::{
+			ButtonState,
+			keyboard::{Key, KeyboardInput},
+			mouse::MouseScrollUnit,
+		},
+		input_focus::InputFocus,
+		picking::events::Scroll,
+		prelude::*,
+	};
+
+	use super::{
+		HtmlTextInput, HtmlTextInputParts, HtmlUiClipboard, html_input_keyboard,
+		html_input_update_display,
+	};
+	use crate::{
+		build::build_tests::{changed_values, hit, pointer, press_key, spawn_form, spawn_laid_out},
+		data::HtmlValue,
+		form::HtmlValueSubmitted,
+		scroll::html_scroll_wheel,
+	};
+
+	/// Spawns `source` with keyboard input and focuses its first element.
+	fn spawn_focused(source: &str) -> (App, Vec<Entity>) {
+		let (mut app, root) = spawn_form(source);
+		app.init_resource::<HtmlUiClipboard>()
+			.init_resource::<ButtonInput<KeyCode>>()
+			.add_message::<HtmlValueSubmitted>()
+			.add_systems(Update, html_input_keyboard);
+		let inputs = app.world().get::<Children>(root).unwrap().to_vec();
+		app.world_mut().resource_mut::<InputFocus>().set(inputs[0]);
+		(app, inputs)
+	}
+
+	fn type_text(app: &mut App, text: &str) {
+		for char in text.chars() {
+			press_key(app, Key::Character(char.to_string().into()));
+		}
+		app.update();
+	}
+
+	/// Presses `key_code` with `modifiers` held, as a layout producing `text` would.
+	fn press_with(app: &mut App, modifiers: &[KeyCode], key_code: KeyCode, text: Option<&str>) {
+		let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+		for modifier in modifiers {
+			keys.press(*modifier);
+		}
+		app.world_mut().write_message(KeyboardInput {
+			key_code,
+			logical_key: Key::Character(text.unwrap_or("?").into()),
+			state: ButtonState::Pressed,
+			text: text.map(Into::into),
+			repeat: false,
+			window: Entity::PLACEHOLDER,
+		});
+		app.update();
+		app.world_mut()
+			.resource_mut::<ButtonInput<KeyCode>>()
+			.release_all();
+	}
+
+	fn value(app: &App, input: Entity) -> &str {
+		app.world().get::<HtmlTextInput>(input).unwrap().value()
+	}
+
+	/// Typed text goes to the focused input up to its `maxlength`, Enter submits it, and Escape
+	/// leaves it.
+	#[test]
+	fn test_input_keyboard() {
+		let (mut app, inputs) = spawn_focused(r#"<input maxlength="5" /><input />"#);
+
+		type_text(&mut app, "abc");
+		assert_eq!(value(&app, inputs[0]), "abc");
+		let changed = changed_values(&app);
+		assert_eq!(changed.len(), 3);
+		assert_eq!(changed[2].entity, inputs[0]);
+		assert_eq!(changed[2].value, HtmlValue::String("abc".into()));
+
+		type_text(&mut app, "defg");
+		assert_eq!(value(&app, inputs[0]), "abcde");
+		assert_eq!(changed_values(&app).len(), 2);
+		assert_eq!(value(&app, inputs[1]), "");
+
+		press_key(&mut app, Key::Enter);
+		app.update();
+		let submitted: Vec<_> = app
+			.world()
+			.resource::<Messages<HtmlValueSubmitted>>()
+			.iter_current_update_messages()
+			.map(|submitted| (submitted.entity, submitted.value.clone()))
+			.collect();
+		assert_eq!(submitted, [(inputs[0], HtmlValue::String("abcde".into()))]);
+
+		press_key(&mut app, Key::Escape);
+		app.update();
+		assert_eq!(app.world().resource::<InputFocus>().get(), None);
+		type_text(&mut app, "x");
+		assert_eq!(value(&app, inputs[0]), "abcde");
+		assert!(changed_values(&app).is_empty());
+	}
+
+	/// Ctrl+C, Ctrl+X and Ctrl+V go through the clipboard, while AltGr, sent as Ctrl+Alt, types.
+	#[test]
+	fn test_input_clipboard() {
+		let (mut app, inputs) = spawn_focused(r#"<input value="Hello" />"#);
+		let ctrl = [KeyCode::ControlLeft];
+
+		press_with(&mut app, &ctrl, KeyCode::KeyA, None);
+		press_with(&mut app, &ctrl, KeyCode::KeyC, None);
+		let mut clipboard = app.world_mut().resource_mut::<HtmlUiClipboard>();
+		assert_eq!(clipboard.0.read().as_deref(), Some("Hello"));
+		assert_eq!(value(&app, inputs[0]), "Hello");
+
+		press_with(&mut app, &ctrl, KeyCode::KeyX, None);
+		assert_eq!(value(&app, inputs[0]), "");
+		press_with(&mut app, &ctrl, KeyCode::KeyV, None);
+		press_with(&mut app, &ctrl, KeyCode::KeyV, None);
+		assert_eq!(value(&app, inputs[0]), "HelloHello");
+
+		press_with(
+			&mut app,
+			&[KeyCode::ControlLeft, KeyCode::AltRight],
+			KeyCode::KeyQ,
+			Some("@"),
+		);
+		assert_eq!(value(&app, inputs[0]), "HelloHello@");
+	}
+
+	/// The mouse wheel scrolls a `<textarea>`, which stays scrolled while its caret does not move.
+	#[test]
+	fn test_textarea_wheel() {
+		let (mut app, root) = spawn_laid_out(r#"<textarea rows="2">Notes</textarea>"#);
+		app.init_resource::<InputFocus>()
+			.add_systems(Update, html_input_update_display)
+			.add_observer(html_scroll_wheel);
+		let textarea = app.world().get::<Children>(root).unwrap()[0];
+		// Text is not measured here, so the content stands in for many lines.
+		let content = app
+			.world()
+			.get::<HtmlTextInputParts>(textarea)
+			.unwrap()
+			.content;
+		app.world_mut().get_mut::<Node>(content).unwrap().min_height = Val::Px(200.0);
+		app.update();
+		app.update();
+		assert_eq!(app.world().get::<ScrollPosition>(textarea).unwrap().y, 0.0);
+
+		app.world_mut().trigger(pointer(
+			textarea,
+			Scroll {
+				unit: MouseScrollUnit::Line,
+				x: 0.0,
+				y: -1.0,
+				hit: hit(),
+			},
+		));
+		app.update();
+		app.update();
+		assert_eq!(app.world().get::<ScrollPosition>(textarea).unwrap().y, 20.0);
+	}
+}
@@ -0,0 +1,287 @@
+use bevy::prelude::*;
+
+use crate::{ast::HtmlElement, error::HtmlUiError, parser::parse_val};
+
+/// Applies the layout attributes of `element`, such as `width="200px"` or
+/// `justify-content="space-between"`, to the `Node` of any tag, over what its handler set. The
+/// attributes in `consumed` belong to the handler instead.
+pub(crate) fn insert_layout_attributes(
+	entity: &mut EntityWorldMut,
+	element: &HtmlElement,
+	consumed: &[&str],
+) {
+	let Some(mut node) = entity.get_mut::<Node>() else {
+		return;
+	};
+	for (name, value) in &element.attributes {
+		if consumed.contains(&name.as_str()) {
+			continue;
+		}
+		if let Err(err) = apply_layout_attribute(&mut node, name, value.trim()) {
+			warn!("invalid `{name}` on <{}>: {err}", element.tag.as_str());
+		}
+	}
+}
+
+/// Sets the `Node` field named by the CSS property `name`. Other attributes are left alone.
+#[allow(clippy::too_many_lines)]
+fn apply_layout_attribute(node: &mut Node, name: &str, value: &str) -> Result<(), HtmlUiError> {
+	match name {
+		"width" => node.width = parse_val(value)?,
+		"height" => node.height = parse_val(value)?,
+		"min-width" => node.min_width = parse_val(value)?,
+		"min-height" => node.min_height = parse_val(value)?,
+		"max-width" => node.max_width = parse_val(value)?,
+		"max-height" => node.max_height = parse_val(value)?,
+		"top" => node.top = parse_val(value)?,
+		"left" => node.left = parse_val(value)?,
+		"right" => node.right = parse_val(value)?,
+		"bottom" => node.bottom = parse_val(value)?,
+		"padding" => node.padding = parse_rect(value)?,
+		"margin" => node.margin = parse_rect(value)?,
+		"border" => node.border = parse_rect(value)?,
+		"flex-grow" => node.flex_grow = parse_number(value)?,
+		"flex-shrink" => node.flex_shrink = parse_number(value)?,
+		"flex-basis" => node.flex_basis = parse_val(value)?,
+		"aspect-ratio" => node.aspect_ratio = parse_aspect_ratio(value)?,
+		"display" => {
+			node.display = keyword(
+				value,
+				&[
+					("flex", Display::Flex),
+					("grid", Display::Grid),
+					("block", Display::Block),
+					("none", Display::None),
+				],
+			)?;
+		}
+		"position" => {
+			node.position_type = keyword(
+				value,
+				&[
+					("relative", PositionType::Relative),
+					("absolute", PositionType::Absolute),
+				],
+			)?;
+		}
+		"flex-direction" => {
+			node.flex_direction = keyword(
+				value,
+				&[
+					("row", FlexDirection::Row),
+					("column", FlexDirection::Column),
+					("row-reverse", FlexDirection::RowReverse),
+					("column-reverse", FlexDirection::ColumnReverse),
+				],
+			)?;
+		}
+		"flex-wrap" | "wrap" => {
+			node.flex_wrap = keyword(
+				value,
+				&[
+					("nowrap", FlexWrap::NoWrap),
+					("wrap", FlexWrap::Wrap),
+					("wrap-reverse", FlexWrap::WrapReverse),
+				],
+			)?;
+		}
+		"align-items" => {
+			node.align_items = keyword(
+				value,
+				&[
+					("start", AlignItems::Start),
+					("end", AlignItems::End),
+					("flex-start", AlignItems::FlexStart),
+					("flex-end", AlignItems::FlexEnd),
+					("center", AlignItems::Center),
+					("baseline", AlignItems::Baseline),
+					("stretch", AlignItems::Stretch),
+				],
+			)?;
+		}
+		"align-self" => {
+			node.align_self = keyword(
+				value,
+				&[
+					("auto", AlignSelf::Auto),
+					("start", AlignSelf::Start),
+					("end", AlignSelf::End),
+					("flex-start", AlignSelf::FlexStart),
+					("flex-end", AlignSelf::FlexEnd),
+					("center", AlignSelf::Center),
+					("baseline", AlignSelf::Baseline),
+					("stretch", AlignSelf::Stretch),
+				],
+			)?;
+		}
+		"align-content" => {
+			node.align_content = keyword(
+				value,
+				&[
+					("start", AlignContent::Start),
+					("end", AlignContent::End),
+					("flex-start", AlignContent::FlexStart),
+					("flex-end", AlignContent::FlexEnd),
+					("center", AlignContent::Center),
+					("stretch", AlignContent::Stretch),
+					("space-between", AlignContent::SpaceBetween),
+					("space-evenly", AlignContent::SpaceEvenly),
+					("space-around", AlignContent::SpaceAround),
+				],
+			)?;
+		}
+		"justify-content" => {
+			node.justify_content = keyword(
+				value,
+				&[
+					("start", JustifyContent::Start),
+					("end", JustifyContent::End),
+					("flex-start", JustifyContent::FlexStart),
+					("flex-end", JustifyContent::FlexEnd),
+					("center", JustifyContent::Center),
+					("stretch", JustifyContent::Stretch),
+					("space-between", JustifyContent::SpaceBetween),
+					("space-evenly", JustifyContent::SpaceEvenly),
+					("space-around", JustifyContent::SpaceAround),
+				],
+			)?;
+		}
+		"overflow" => node.overflow = parse_overflow(value)?,
+		_ => {}
+	}
+	Ok(())
+}
+
+/// Matches `value` against the keywords of a CSS property.
+fn keyword<T: Copy>(value: &str, keywords: &[(&str, T)]) -> Result<T, HtmlUiError> {
+	keywords
+		.iter()
+		.find(|(name, _)| *name == value)
+		.map(|(_, keyword)| *keyword)
+		.ok_or_else(|| {
+			let expected: Vec<&str> = keywords.iter().map(|(name, _)| *name).collect();
+			HtmlUiError::ParseError(format!(
+				"unknown value `{value}`, expected one of {}",
+				expected.join(", ")
+			))
+		})
+}
+
+fn parse_number(value: &str) -> Result<f32, HtmlUiError> {
+	value
+		.parse()
+		.map_err(|err| HtmlUiError::ParseError(format!("invalid number `{value}`: {err}")))
+}
+
+/// Parses one to four lengths in CSS order (top, right, bottom, left), as in `padding="4px 8px"`.
+fn parse_rect(value: &str) -> Result<UiRect, HtmlUiError> {
+	let values = value
+		.split_whitespace()
+		.map(parse_val)
+		.collect::<Result<Vec<_>, _>>()?;
+	let (top, right, bottom, left) = match values[..] {
+		[all] => (all, all, all, all),
+		[vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+		[top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+		[top, right, bottom, left] => (top, right, bottom, left),
+		_ => {
+			return Err(HtmlUiError::ParseError(format!(
+				"invalid `{value}`, expected one to four lengths"
+			)));
+		}
+	};
+	Ok(UiRect::new(left, right, top, bottom))
+}
+
+/// Parses `auto`, a ratio such as `16 / 9`, or a number.
+fn parse_aspect_ratio(value: &str) -> Result<Option<f32>, HtmlUiError> {
+	if value == "auto" {
+		return Ok(None);
+	}
+	match value.split_once('/') {
+		Some((width, height)) => Ok(Some(
+			parse_number(width.trim())? / parse_number(height.trim())?,
+		)),
+		None => parse_number(value).map(Some),
+	}
+}
+
+/// Parses one overflow keyword for both axes, or one for each as in `overflow="clip visible"`.
+fn parse_overflow(value: &str) -> Result<Overflow, HtmlUiError> {
+	let axis = |value: &str| {
+		keyword(
+			value,
+			&[
+				("visible", OverflowAxis::Visible),
+				("clip", OverflowAxis::Clip),
+				("hidden", OverflowAxis::Hidden),
+				("scroll", OverflowAxis::Scroll),
+			],
+		)
+	};
+	let mut words = value.split_whitespace();
+	match (words.next(), words.next(), words.next()) {
+		(Some(both), None, None) => {
+			let both = axis(both)?;
+			Ok(Overflow { x: both, y: both })
+		}
+		(Some(x), Some(y), None) => Ok(Overflow {
+			x: axis(x)?,
+			y: axis(y)?,
+		}),
+		_ => Err(HtmlUiError::ParseError(format!(
+			"invalid overflow `{value}`, expected one or two keywords"
+		))),
+	}
+}
+
+#[cfg(test)]
+mod layout_tests {
+	use bevy::prelude::*;
+
+	use super::{apply_layout_attribute, insert_layout_attributes};
+	use crate::{ast::HtmlNode, parser::parse_htmlish};
+
+	#[test]
+	fn test_layout_attributes() {
+		let mut node = Node::default();
+		for (name, value) in [
+			("width", "50%"),
+			("padding", "4px 8px"),
+			("justify-content", "space-between"),
+			("flex-grow", "1"),
+			("aspect-ratio", "16 / 9"),
+			("overflow", "clip visible"),
+			("wrap", "wrap"),
+			("placeholder", "ignored"),
+		] {
+			apply_layout_attribute(&mut node, name, value).unwrap();
+		}
+		assert_eq!(node.width, Val::Percent(50.0));
+		assert_eq!(node.padding, UiRect::axes(Val::Px(8.0), Val::Px(4.0)));
+		assert_eq!(node.justify_content, JustifyContent::SpaceBetween);
+		assert_eq!(node.flex_grow, 1.0);
+		assert_eq!(node.aspect_ratio, Some(16.0 / 9.0));
+		assert_eq!(node.overflow.x, OverflowAxis::Clip);
+		assert_eq!(node.overflow.y, OverflowAxis::Visible);
+		assert_eq!(node.flex_wrap, FlexWrap::Wrap);
+
+		assert!(apply_layout_attribute(&mut node, "position", "fixed").is_err());
+		assert!(apply_layout_attribute(&mut node, "margin", "1px 2px 3px 4px 5px").is_err());
+	}
+
+	/// Attributes that a handler reads itself are not applied to the `Node`.
+	#[test]
+	fn test_consumed_attributes() {
+		let nodes = parse_htmlish(&r#"<meter width="3" height="20px"></meter>"#.into()).unwrap();
+		let [HtmlNode::Element(element)] = &nodes[..] else {
+			panic!("expected one element");
+		};
+		let mut world = World::new();
+		let mut entity = world.spawn(Node::default());
+		insert_layout_attributes(&mut entity, element, &["width"]);
+		let node = entity.get::<Node>().unwrap();
+		assert_eq!(node.width, Val::Auto);
+		assert_eq!(node.height, Val::Px(20.0));
+	}
+}
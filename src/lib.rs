@@ -1,17 +1,89 @@
 mod asset;
 mod ast;
+mod bindings;
 mod build;
 mod callbacks;
+mod checkbox;
+#[cfg(feature = "codegen")]
+mod codegen;
+mod data;
+mod direction;
 mod error;
+mod form;
+mod grid;
+mod icon;
+mod img;
+mod input;
+mod layout;
 mod loader;
+mod locale;
 mod parser;
 mod plugin;
+mod progress;
+mod radio;
 mod resources;
+mod scroll;
+mod select;
 mod settings;
+mod slider;
+mod span;
+mod tags;
+mod template;
+mod text_edit;
+mod translations;
 
 pub use asset::HtmlUiAsset;
+pub use ast::{HtmlEach, HtmlElement, HtmlNode, HtmlTag};
 pub use build::HtmlUiRoot;
 pub use callbacks::HtmlCallback;
+pub use checkbox::HtmlCheckbox;
+#[cfg(feature = "codegen")]
+pub use codegen::{build_html_ui, build_html_ui_with_tags};
+pub use data::{HtmlUiData, HtmlValue};
+pub use direction::HtmlDirection;
 pub use error::HtmlUiError;
+pub use form::{HtmlValueChanged, HtmlValueSubmitted};
+pub use icon::{HtmlInputDevice, HtmlKey, HtmlKeyGlyphs};
+pub use input::{HtmlClipboard, HtmlTextInput, HtmlUiClipboard};
+pub use locale::HtmlLocale;
 pub use plugin::HtmlUiPlugin;
+pub use progress::HtmlProgress;
+pub use radio::{HtmlRadio, HtmlRadioGroup};
 pub use resources::HtmlCssUiResource;
+pub use scroll::HtmlScroll;
+pub use select::{HtmlOption, HtmlSelect};
+pub use slider::HtmlSlider;
+pub use tags::{HtmlTagHandler, HtmlTagRegistry, HtmlUiAppExt};
+pub use translations::{HtmlFluentLoader, HtmlKeyValueLoader, HtmlTranslations};
+
+/// Includes the document generated by `build_html_ui` for `path`, as an expression of type
+/// [`HtmlUiAsset`] spawning its entity tree. `build_html_ui` needs the `codegen` feature in the
+/// build dependency, while `html_ui!` is always available.
+///
+/// ```ignore
+/// // build.rs
+/// bevy_flair_html_extension::build_html_ui("assets/ui/menu.html").unwrap();
+///
+/// // main.rs
+/// let html = assets.add(html_ui!("assets/ui/menu.html"));
+/// ```
+#[macro_export]
+macro_rules! html_ui {
+	($path:literal) => {{
+		use $crate::__codegen as __html_ui;
+		include!(concat!(env!("OUT_DIR"), "/html_ui/", $path, ".rs"))
+	}};
+}
+
+#[doc(hidden)]
+pub mod __codegen {
+	pub use bevy::{
+		ecs::{entity::Entity, world::World},
+		ui::Val,
+	};
+
+	pub use crate::{
+		HtmlEach, HtmlElement, HtmlNode, HtmlTag, HtmlUiAsset,
+		build::{begin_element, end_element, spawn_node, spawn_text},
+	};
+}
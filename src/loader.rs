@@ -1,14 +1,42 @@
+use std::{collections::HashMap, fmt::Write as _, path::PathBuf};
+
 use bevy::{
-	asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+	asset::{
+		Asset, AssetLoader, AssetPath, AsyncReadExt, Deferred, Handle, LoadContext,
+		LoadDirectError, NestedLoader, StaticTyped,
+		io::{AssetSourceId, Reader, VecReader},
+	},
+	image::Image,
+	log::warn,
 	reflect::TypePath,
 };
+use bevy_flair::style::StyleSheet;
 
 use crate::{
-	asset::HtmlUiAsset, error::HtmlUiError, parser::parse_htmlish, settings::HtmlUiSettings,
+	asset::HtmlUiAsset,
+	ast::{HtmlNode, HtmlTag},
+	error::HtmlUiError,
+	parser::parse_htmlish,
+	settings::HtmlUiSettings,
+	tags::HtmlTagNames,
+	template::expand_templates,
 };
 
-#[derive(TypePath)]
-pub struct HtmlUiLoader;
+/// How deeply `<include>`s may nest, which stops include cycles such as a.html → b.html → a.html.
+pub(crate) const MAX_INCLUDE_DEPTH: usize = 32;
+
+#[derive(TypePath, Default)]
+pub struct HtmlUiLoader {
+	/// The registered tags, which every tag left after expanding templates must be one of. Without
+	/// them, unknown tags are only reported when spawning.
+	tags: Option<HtmlTagNames>,
+}
+
+impl HtmlUiLoader {
+	pub(crate) fn new(tags: HtmlTagNames) -> Self {
+		Self { tags: Some(tags) }
+	}
+}
 
 impl AssetLoader for HtmlUiLoader {
 	type Asset = HtmlUiAsset;
@@ -18,8 +46,8 @@ impl AssetLoader for HtmlUiLoader {
 	async fn load(
 		&self,
 		reader: &mut dyn Reader,
-		_settings: &Self::Settings,
-		_load_context: &mut LoadContext<'_>,
+		settings: &Self::Settings,
+		load_context: &mut LoadContext<'_>,
 	) -> Result<Self::Asset, Self::Error> {
 		let mut source = String::new();
 		reader
@@ -27,9 +55,94 @@ impl AssetLoader for HtmlUiLoader {
 			.await
 			.map_err(HtmlUiError::IoError)?;
 
-		let ast = parse_htmlish(&source)?;
+		let mut ast = parse_htmlish(&source)?;
+
+		let mut srcs = Vec::new();
+		collect_includes(&ast, &mut srcs)?;
+		if !srcs.is_empty() && settings.include_depth >= MAX_INCLUDE_DEPTH {
+			return Err(HtmlUiError::IncludeError(
+				srcs.swap_remove(0),
+				"includes are nested too deeply".into(),
+			));
+		}
+
+		let mut included_asts = HashMap::new();
+		let mut includes = Vec::new();
+		for src in srcs {
+			if included_asts.contains_key(&src) {
+				continue;
+			}
+
+			let path = load_context
+				.path()
+				.resolve_embed(&src)
+				.map_err(|err| HtmlUiError::IncludeError(src.clone(), err.to_string()))?;
+			if path == *load_context.path() {
+				return Err(HtmlUiError::IncludeError(
+					src,
+					"a document cannot include itself".into(),
+				));
+			}
+
+			let include_depth = settings.include_depth + 1;
+			let with_depth = move |settings: &mut HtmlUiSettings| {
+				settings.include_depth = include_depth;
+			};
+			let loaded = load_immediately::<HtmlUiAsset>(
+				load_context.loader().with_settings(with_depth),
+				&path,
+			)
+			.await
+			.map_err(|err| HtmlUiError::IncludeError(src.clone(), err.to_string()))?;
+
+			includes.push(load_context.loader().with_settings(with_depth).load(path));
+			// The styles go where the include is, so that later styles of this document override them.
+			let mut nodes = loaded.templates;
+			nodes.extend(loaded.styles);
+			nodes.extend(loaded.ast);
+			included_asts.insert(src, nodes);
+		}
+
+		if !included_asts.is_empty() {
+			ast = expand_includes(ast, &included_asts);
+		}
+
+		let (mut ast, mut templates) = expand_templates(ast)?;
+		if let Some(tags) = &self.tags {
+			check_tags(&ast, tags)?;
+		}
 
-		let asset = HtmlUiAsset { source, ast };
+		// Resolved after expansion, so that paths passed to templates are resolved too. Included
+		// nodes were already resolved relative to their own document, which is kept.
+		let document_path = load_context.path().clone_owned();
+		let mut dependencies = Vec::new();
+		let mut links = Vec::new();
+		let mut on_asset = |kind, path: &AssetPath<'static>| match kind {
+			AssetKind::Image => {
+				dependencies.push(load_context.load::<Image>(path.clone()).untyped());
+			}
+			AssetKind::StyleSheet => {
+				dependencies.push(load_context.load::<StyleSheet>(path.clone()).untyped());
+			}
+			AssetKind::Link => links.push(path.clone()),
+		};
+		resolve_asset_attributes(&mut ast, &document_path, &mut on_asset)?;
+		resolve_asset_attributes(&mut templates, &document_path, &mut on_asset)?;
+
+		let styles = take_styles(&mut ast);
+		let style_sheet =
+			load_style_sheet(load_context, &document_path, &links, style_css(&styles)).await?;
+
+		let asset = HtmlUiAsset {
+			source,
+			ast,
+			templates,
+			styles,
+			includes,
+			dependencies,
+			style_sheet,
+			spawn: None,
+		};
 
 		Ok(asset)
 	}
@@ -38,3 +151,531 @@ impl AssetLoader for HtmlUiLoader {
 		&["html"]
 	}
 }
+
+/// Fails on the first custom tag in `nodes` that is not registered, such as a misspelled `<vbxo>`.
+/// Templates are expanded beforehand, so any custom tag left is not one.
+pub(crate) fn check_tags(nodes: &[HtmlNode], tags: &HtmlTagNames) -> Result<(), HtmlUiError> {
+	for node in nodes {
+		let HtmlNode::Element(element) = node else {
+			continue;
+		};
+		if let HtmlTag::Named(name) = &element.tag
+			&& !tags.contains(name)
+		{
+			return Err(HtmlUiError::ParseError(format!(
+				"unknown tag `<{name}>`, which is neither a registered tag nor a template"
+			)));
+		}
+		check_tags(&element.children, tags)?;
+	}
+	Ok(())
+}
+
+/// Loads the asset at `path` while loading a document. Loading immediately, rather than only taking
+/// a handle, makes the document reload whenever that asset changes.
+async fn load_immediately<A: Asset>(
+	loader: NestedLoader<'_, '_, StaticTyped, Deferred>,
+	path: &AssetPath<'static>,
+) -> Result<A, LoadDirectError> {
+	Ok(loader.immediate().load::<A>(path.clone()).await?.take())
+}
+
+/// Loads the `<link>`ed stylesheets and `<style>` blocks of a document as its `style` sub-asset, with
+/// the links `@import`ed ahead of the blocks, or returns `None` if it has neither.
+async fn load_style_sheet(
+	load_context: &mut LoadContext<'_>,
+	document_path: &AssetPath<'static>,
+	links: &[AssetPath<'static>],
+	css: String,
+) -> Result<Option<Handle<StyleSheet>>, HtmlUiError> {
+	if links.is_empty() && css.is_empty() {
+		return Ok(None);
+	}
+
+	let mut imports = String::new();
+	for link in links {
+		load_immediately::<StyleSheet>(load_context.loader(), link)
+			.await
+			.map_err(|err| HtmlUiError::StyleSheetError(link.to_string(), err.to_string()))?;
+		let _ = writeln!(imports, "@import \"{link}\";");
+	}
+
+	// Loaded under a path of its own, so that the document does not end up depending on itself,
+	// which is also the name CSS errors are reported with.
+	let mut css_path = document_path.path().as_os_str().to_owned();
+	css_path.push(".css");
+	let css_path =
+		AssetPath::from(PathBuf::from(css_path)).with_source(document_path.source().clone_owned());
+	let mut reader = VecReader::new((imports + &css).into_bytes());
+	let loaded = load_context
+		.loader()
+		.immediate()
+		.with_reader(&mut reader)
+		.load::<StyleSheet>(css_path.clone())
+		.await
+		.map_err(|err| HtmlUiError::StyleSheetError(css_path.to_string(), err.to_string()))?;
+	Ok(Some(load_context.add_loaded_labeled_asset("style", loaded)))
+}
+
+pub(crate) fn collect_includes(
+	nodes: &[HtmlNode],
+	srcs: &mut Vec<String>,
+) -> Result<(), HtmlUiError> {
+	for node in nodes {
+		let HtmlNode::Element(element) = node else {
+			continue;
+		};
+		if element.tag == HtmlTag::Include {
+			let src = element
+				.attribute("src")
+				.filter(|src| !src.is_empty())
+				.ok_or_else(|| HtmlUiError::ParseError("<include> without a src".into()))?;
+			srcs.push(src.to_owned());
+		} else {
+			collect_includes(&element.children, srcs)?;
+		}
+	}
+	Ok(())
+}
+
+pub(crate) fn expand_includes(
+	nodes: Vec<HtmlNode>,
+	included: &HashMap<String, Vec<HtmlNode>>,
+) -> Vec<HtmlNode> {
+	let mut expanded = Vec::with_capacity(nodes.len());
+	for node in nodes {
+		match node {
+			HtmlNode::Element(element) if element.tag == HtmlTag::Include => {
+				if let Some(nodes) = element.attribute("src").and_then(|src| included.get(src)) {
+					expanded.extend(nodes.iter().cloned());
+				}
+			}
+			HtmlNode::Element(mut element) => {
+				element.children = expand_includes(element.children, included);
+				expanded.push(HtmlNode::Element(element));
+			}
+			HtmlNode::Text(_) => expanded.push(node),
+		}
+	}
+	expanded
+}
+
+/// Removes every `<style>` and `<link>` element from `nodes`, returning the stylesheet ones in
+/// document order. Links are picked up beforehand by [`resolve_asset_attributes`].
+pub(crate) fn take_styles(nodes: &mut Vec<HtmlNode>) -> Vec<HtmlNode> {
+	let mut styles = Vec::new();
+	take_styles_into(nodes, &mut styles);
+	styles
+}
+
+fn take_styles_into(nodes: &mut Vec<HtmlNode>, styles: &mut Vec<HtmlNode>) {
+	let mut index = 0;
+	while index < nodes.len() {
+		let HtmlNode::Element(element) = &mut nodes[index] else {
+			index += 1;
+			continue;
+		};
+		match element.tag {
+			HtmlTag::Link => {
+				if element.attribute("rel") == Some("stylesheet") {
+					styles.push(nodes.remove(index));
+				} else {
+					warn!("ignoring <link> without rel=\"stylesheet\"");
+					nodes.remove(index);
+				}
+			}
+			HtmlTag::Style => styles.push(nodes.remove(index)),
+			_ => {
+				take_styles_into(&mut element.children, styles);
+				index += 1;
+			}
+		}
+	}
+}
+
+/// The CSS of the `<style>`s among `styles`, joined in document order.
+pub(crate) fn style_css(styles: &[HtmlNode]) -> String {
+	let mut css = String::new();
+	for style in styles {
+		let HtmlNode::Element(element) = style else {
+			continue;
+		};
+		if element.tag != HtmlTag::Style {
+			continue;
+		}
+		for child in &element.children {
+			if let HtmlNode::Text(text) = child {
+				css.push_str(text);
+				css.push('\n');
+			}
+		}
+	}
+	css
+}
+
+/// The type of asset an attribute refers to.
+#[derive(Clone, Copy)]
+pub(crate) enum AssetKind {
+	Image,
+	StyleSheet,
+	/// The `href` of a `<link rel="stylesheet">`, merged into the document's own stylesheet.
+	Link,
+}
+
+fn asset_attribute(tag: &HtmlTag, name: &str, rel: Option<&str>) -> Option<AssetKind> {
+	match (tag.as_str(), name) {
+		("img" | "icon", "src") | (_, "image") => Some(AssetKind::Image),
+		(_, "stylesheet") => Some(AssetKind::StyleSheet),
+		("link", "href") if rel == Some("stylesheet") => Some(AssetKind::Link),
+		_ => None,
+	}
+}
+
+/// Rewrites every attribute holding an asset path to that path resolved relative to `document`,
+/// and passes it to `on_asset` so it can be loaded as a dependency.
+///
+/// Resolved paths are rooted with a leading `/`, so resolving them again, as the documents including
+/// this one do, leaves them unchanged. Paths containing `{{ ... }}` are left alone, since they are
+/// only known once interpolated.
+pub(crate) fn resolve_asset_attributes(
+	nodes: &mut [HtmlNode],
+	document: &AssetPath<'static>,
+	on_asset: &mut impl FnMut(AssetKind, &AssetPath<'static>),
+) -> Result<(), HtmlUiError> {
+	for node in nodes {
+		let HtmlNode::Element(element) = node else {
+			continue;
+		};
+		let rel = element.attribute("rel").map(str::to_owned);
+		for (name, value) in &mut element.attributes {
+			let Some(kind) = asset_attribute(&element.tag, name, rel.as_deref()) else {
+				continue;
+			};
+			if value.is_empty() || value.contains("{{") {
+				continue;
+			}
+			let path = document.resolve_embed(value).map_err(|err| {
+				HtmlUiError::ParseError(format!("invalid asset path `{value}`: {err}"))
+			})?;
+			on_asset(kind, &path);
+			*value = if matches!(path.source(), AssetSourceId::Default) {
+				format!("/{path}")
+			} else {
+				path.to_string()
+			};
+		}
+		resolve_asset_attributes(&mut element.children, document, on_asset)?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod loader_tests {
+	use std::{path::Path, thread, time::Duration};
+
+	use bevy::{
+		asset::{
+			AssetPath, LoadState,
+			io::{
+				AssetSourceBuilder, AssetSourceId,
+				memory::{Dir, MemoryAssetReader},
+			},
+		},
+		prelude::*,
+		time::TimePlugin,
+	};
+	use bevy_flair::{FlairPlugin, prelude::NodeStyleSheet};
+
+	use crate::{
+		asset::HtmlUiAsset,
+		ast::{HtmlElement, HtmlNode},
+		build::spawn_html_ui,
+		error::HtmlUiError,
+		parser::parse_htmlish,
+		resources::HtmlCssUiResource,
+		tags::{HtmlTagRegistry, register_builtin_tags},
+	};
+
+	use super::{
+		AssetKind, HtmlUiLoader, collect_includes, resolve_asset_attributes, style_css, take_styles,
+	};
+
+	/// An app loading documents from `files` in memory, with the built-in tags registered.
+	pub(crate) fn document_app(files: &[(&str, &str)]) -> App {
+		let dir = Dir::default();
+		for (file, source) in files {
+			dir.insert_asset_text(Path::new(file), source);
+		}
+		let mut app = App::new();
+		app.register_asset_source(
+			AssetSourceId::Default,
+			AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+		)
+		.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+		.init_asset::<HtmlUiAsset>()
+		.init_asset::<Image>()
+		.init_resource::<HtmlTagRegistry>();
+		register_builtin_tags(&mut app);
+		let tags = app.world().resource::<HtmlTagRegistry>().names();
+		app.register_asset_loader(HtmlUiLoader::new(tags));
+		app
+	}
+
+	/// Updates `app` until `handle` has loaded or failed, returning whether it loaded.
+	pub(crate) fn wait_for_load<A: Asset>(app: &mut App, handle: &Handle<A>) -> bool {
+		for _ in 0..1000 {
+			app.update();
+			match app.world().resource::<AssetServer>().load_state(handle) {
+				LoadState::Loaded => return true,
+				LoadState::Failed(_) => return false,
+				_ => {}
+			}
+			thread::sleep(Duration::from_millis(1));
+		}
+		panic!("`{:?}` neither loaded nor failed", handle.path());
+	}
+
+	/// Loads `path` from the documents in `files`, returning its nodes if it loaded.
+	fn load_documents(files: &[(&str, &str)], path: &'static str) -> Option<Vec<HtmlNode>> {
+		let mut app = document_app(files);
+		let handle = app
+			.world()
+			.resource::<AssetServer>()
+			.load::<HtmlUiAsset>(path);
+		wait_for_load(&mut app, &handle).then(|| {
+			let assets = app.world().resource::<Assets<HtmlUiAsset>>();
+			assets.get(&handle).unwrap().ast.clone()
+		})
+	}
+
+	/// Loads `path` from `files` under a bevy_flair app, spawns it, and returns its root once styles
+	/// are applied.
+	fn spawn_styled_document(files: &[(&str, &str)], path: &'static str) -> (App, Entity) {
+		let mut app = document_app(files);
+		app.add_plugins((TimePlugin, WindowPlugin::default(), FlairPlugin))
+			.finish();
+		let handle = app
+			.world()
+			.resource::<AssetServer>()
+			.load::<HtmlUiAsset>(path);
+		assert!(wait_for_load(&mut app, &handle), "`{path}` failed to load");
+		app.insert_resource(HtmlCssUiResource::new(handle.clone(), None));
+		let root = spawn_html_ui(app.world_mut(), handle.id()).unwrap();
+		app.update();
+		(app, root)
+	}
+
+	#[test]
+	fn test_includes() {
+		let files = [
+			("menu.html", r#"<ui><include src="header.html" /></ui>"#),
+			("header.html", "<label>Header</label>"),
+			("a.html", r#"<include src="b.html" />"#),
+			("b.html", r#"<include src="a.html" />"#),
+		];
+		assert!(load_documents(&files, "menu.html").is_some());
+		assert!(load_documents(&files, "a.html").is_none());
+
+		let ast = parse_htmlish(&"<ui><include /></ui>".into()).unwrap();
+		assert!(matches!(
+			collect_includes(&ast, &mut Vec::new()),
+			Err(HtmlUiError::ParseError(_))
+		));
+	}
+
+	#[test]
+	fn test_resolve_after_expansion() {
+		let files = [
+			(
+				"ui/menu.html",
+				r#"<template name="my-card" params="icon"><img src="{{ icon }}" /></template><ui><my-card icon="icons/sword.png" /><include src="widgets/header.html" /></ui>"#,
+			),
+			("ui/widgets/header.html", r#"<img src="logo.png" />"#),
+		];
+		let ast = load_documents(&files, "ui/menu.html").unwrap();
+
+		let mut srcs = Vec::new();
+		collect_srcs(&ast, &mut srcs);
+		assert_eq!(srcs, vec!["/ui/icons/sword.png", "/ui/widgets/logo.png"]);
+	}
+
+	#[test]
+	fn test_unknown_tags() {
+		let files = [
+			("typo.html", "<ui><vbxo></vbxo></ui>"),
+			(
+				"card.html",
+				r#"<template name="my-card"><label>Card</label></template><ui><my-card /></ui>"#,
+			),
+		];
+		assert!(load_documents(&files, "typo.html").is_none());
+		assert!(load_documents(&files, "card.html").is_some());
+	}
+
+	fn collect_srcs(nodes: &[HtmlNode], srcs: &mut Vec<String>) {
+		for node in nodes {
+			if let HtmlNode::Element(element) = node {
+				srcs.extend(element.attribute("src").map(str::to_owned));
+				collect_srcs(&element.children, srcs);
+			}
+		}
+	}
+
+	#[test]
+	fn test_take_styles() {
+		let mut ast = parse_htmlish(
+			&r#"<link rel="stylesheet" href="menu.css"><style>.a { width: 10px; }</style><ui stylesheet="widgets/fancy.css"><style>.b { width: 20px; }</style><label>Hi</label></ui>"#
+				.into(),
+		)
+		.unwrap();
+		let mut links = Vec::new();
+		let mut style_sheets = Vec::new();
+		resolve_asset_attributes(
+			&mut ast,
+			&AssetPath::from("ui/menu.html"),
+			&mut |kind, path| match kind {
+				AssetKind::Link => links.push(path.to_string()),
+				AssetKind::StyleSheet => style_sheets.push(path.to_string()),
+				AssetKind::Image => {}
+			},
+		)
+		.unwrap();
+		assert_eq!(links, vec!["ui/menu.css"]);
+		assert_eq!(style_sheets, vec!["ui/widgets/fancy.css"]);
+
+		let css = style_css(&take_styles(&mut ast));
+		assert_eq!(css, ".a { width: 10px; }\n.b { width: 20px; }\n");
+		assert_eq!(ast.len(), 1);
+		let HtmlNode::Element(HtmlElement { children, .. }) = &ast[0] else {
+			panic!("<ui> is not Element");
+		};
+		assert_eq!(children.len(), 1);
+	}
+
+	#[test]
+	fn test_resolve_asset_attributes() {
+		let mut ast = parse_htmlish(
+			&r#"<ui><img src="icons/sword.png" /><img src="/shared/a.png" /><img src="{{ icon }}" /></ui>"#
+				.into(),
+		)
+		.unwrap();
+		let document = AssetPath::from("ui/menu.html");
+		let mut loaded = Vec::new();
+		resolve_asset_attributes(&mut ast, &document, &mut |_, path| {
+			loaded.push(path.to_string());
+		})
+		.unwrap();
+		assert_eq!(loaded, vec!["ui/icons/sword.png", "shared/a.png"]);
+
+		let HtmlNode::Element(HtmlElement { children, .. }) = &ast[0] else {
+			panic!("<ui> is not Element");
+		};
+		let srcs: Vec<_> = children
+			.iter()
+			.filter_map(|child| match child {
+				HtmlNode::Element(element) => element.attribute("src"),
+				HtmlNode::Text(_) => None,
+			})
+			.collect();
+		assert_eq!(
+			srcs,
+			vec!["/ui/icons/sword.png", "/shared/a.png", "{{ icon }}"]
+		);
+	}
+
+	/// The `<style>`s of an included document apply where it is included, under those of the
+	/// including document.
+	#[test]
+	fn test_included_styles() {
+		let files = [
+			(
+				"menu.html",
+				r#"<include src="panel.html" /><style>.wide { width: 40px; }</style>"#,
+			),
+			(
+				"panel.html",
+				r#"<style>.title, .wide { height: 30px; width: 20px; }</style><node class="title"></node><node class="wide"></node>"#,
+			),
+		];
+		let (app, root) = spawn_styled_document(&files, "menu.html");
+		let children = app.world().get::<Children>(root).unwrap();
+		let title = app.world().get::<Node>(children[0]).unwrap();
+		assert_eq!((title.width, title.height), (Val::Px(20.0), Val::Px(30.0)));
+		let wide = app.world().get::<Node>(children[1]).unwrap();
+		assert_eq!((wide.width, wide.height), (Val::Px(40.0), Val::Px(30.0)));
+	}
+
+	/// A document's root takes the stylesheet combining its `<link>`ed stylesheets with those of the
+	/// documents it includes, each resolved relative to its own document.
+	#[test]
+	fn test_linked_styles() {
+		let files = [
+			(
+				"ui/menu.html",
+				r#"<link rel="stylesheet" href="menu.css" /><node class="menu"></node><include src="widgets/panel.html" />"#,
+			),
+			("ui/menu.css", ".menu { width: 10px; }"),
+			(
+				"ui/widgets/panel.html",
+				r#"<link rel="stylesheet" href="panel.css" /><node class="panel"></node>"#,
+			),
+			("ui/widgets/panel.css", ".panel, .menu { height: 20px; }"),
+		];
+		let (app, root) = spawn_styled_document(&files, "ui/menu.html");
+		let world = app.world();
+		let handle = world.resource::<HtmlCssUiResource>().html.clone();
+		let asset = world
+			.resource::<Assets<HtmlUiAsset>>()
+			.get(&handle)
+			.unwrap();
+		let Some(NodeStyleSheet::StyleSheet(style_sheet)) = world.get::<NodeStyleSheet>(root)
+		else {
+			panic!("the root has no stylesheet of its own");
+		};
+		assert_eq!(Some(style_sheet), asset.style_sheet.as_ref());
+
+		let children = world.get::<Children>(root).unwrap();
+		let menu = world.get::<Node>(children[0]).unwrap();
+		assert_eq!((menu.width, menu.height), (Val::Px(10.0), Val::Px(20.0)));
+		let panel = world.get::<Node>(children[1]).unwrap();
+		assert_eq!(panel.height, Val::Px(20.0));
+	}
+
+	/// A `stylesheet` attribute, resolved relative to the document, gives the element another
+	/// stylesheet, which its descendants inherit.
+	#[test]
+	fn test_stylesheet_attribute() {
+		let files = [
+			(
+				"ui/menu.html",
+				r#"<link rel="stylesheet" href="menu.css" /><node class="box"></node><panel stylesheet="widgets/fancy.css"><node class="box"></node></panel>"#,
+			),
+			("ui/menu.css", ".box { width: 10px; }"),
+			("ui/widgets/fancy.css", ".box { width: 30px; }"),
+		];
+		let (mut app, root) = spawn_styled_document(&files, "ui/menu.html");
+		let children = app.world().get::<Children>(root).unwrap().to_vec();
+		let (node, panel) = (children[0], children[1]);
+		let Some(NodeStyleSheet::StyleSheet(style_sheet)) =
+			app.world().get::<NodeStyleSheet>(panel)
+		else {
+			panic!("the panel has no stylesheet of its own");
+		};
+		assert_eq!(
+			style_sheet.path().map(ToString::to_string).as_deref(),
+			Some("ui/widgets/fancy.css")
+		);
+		let style_sheet = style_sheet.clone();
+		assert!(wait_for_load(&mut app, &style_sheet));
+		app.update();
+
+		let world = app.world();
+		let inner = world.get::<Children>(panel).unwrap()[0];
+		assert!(matches!(
+			world.get::<NodeStyleSheet>(inner),
+			Some(NodeStyleSheet::Inherited)
+		));
+		assert_eq!(world.get::<Node>(node).unwrap().width, Val::Px(10.0));
+		assert_eq!(world.get::<Node>(inner).unwrap().width, Val::Px(30.0));
+	}
+}
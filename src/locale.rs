@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::{HtmlElement, HtmlNode},
+	bindings::interpolate_for,
+	input::{HtmlTextInput, set_input_placeholder},
+	span::{needs_text_run, text_run},
+	translations::HtmlTranslations,
+};
+
+/// The current language and the translations of each language, which the `t="key"` and
+/// `t:attribute="key"` attributes are looked up in.
+///
+/// ```ignore
+/// commands.insert_resource(
+///     HtmlLocale::new("en")
+///         .with_translations("en", asset_server.load("locales/en.ftl"))
+///         .with_translations("de", asset_server.load("locales/de.lang.ron")),
+/// );
+/// ```
+#[derive(Resource, Default)]
+pub struct HtmlLocale {
+	language: String,
+	translations: HashMap<String, Vec<Handle<HtmlTranslations>>>,
+}
+
+impl HtmlLocale {
+	#[must_use]
+	pub fn new(language: impl Into<String>) -> Self {
+		Self {
+			language: language.into(),
+			translations: HashMap::new(),
+		}
+	}
+
+	/// Adds a file of translations for `language`. A language can have several, which are searched
+	/// in the order they were added.
+	#[must_use]
+	pub fn with_translations(
+		mut self,
+		language: impl Into<String>,
+		translations: Handle<HtmlTranslations>,
+	) -> Self {
+		self.add_translations(language, translations);
+		self
+	}
+
+	pub fn add_translations(
+		&mut self,
+		language: impl Into<String>,
+		translations: Handle<HtmlTranslations>,
+	) {
+		self.translations
+			.entry(language.into())
+			.or_default()
+			.push(translations);
+	}
+
+	#[must_use]
+	pub fn language(&self) -> &str {
+		&self.language
+	}
+
+	/// Switches the language, updating translated text and attributes in place.
+	pub fn set_language(&mut self, language: impl Into<String>) {
+		self.language = language.into();
+	}
+
+	/// Formats the message `key` of the current language with `args`, or returns `None` if none of
+	/// its loaded translations has it.
+	#[must_use]
+	pub fn translate(
+		&self,
+		assets: &Assets<HtmlTranslations>,
+		key: &str,
+		args: &[(String, String)],
+	) -> Option<String> {
+		self.translations
+			.get(&self.language)?
+			.iter()
+			.filter_map(|handle| assets.get(handle))
+			.find_map(|translations| translations.format(&self.language, key, args))
+	}
+
+	/// Whether `id` is one of the translations of the current language.
+	fn is_current(&self, id: AssetId<HtmlTranslations>) -> bool {
+		self.translations
+			.get(&self.language)
+			.is_some_and(|handles| handles.iter().any(|handle| handle.id() == id))
+	}
+}
+
+/// Text set from the message of a `t="key"` attribute, re-translated when the language, the
+/// translations or the data of its arguments change.
+#[derive(Component)]
+pub(crate) struct HtmlTranslatedText {
+	key: String,
+	args: Vec<(String, String)>,
+	/// The text of the element, shown until a translation is found.
+	fallback: String,
+}
+
+/// The `t:attribute="key"` translations of an element, re-applied in place like
+/// [`HtmlTranslatedText`].
+#[derive(Component)]
+pub(crate) struct HtmlTranslatedAttributes {
+	attributes: Vec<TranslatedAttribute>,
+	args: Vec<(String, String)>,
+}
+
+struct TranslatedAttribute {
+	name: String,
+	key: String,
+	/// The translation last applied, which an `<input>` value is only replaced while it still shows.
+	value: String,
+}
+
+/// The arguments of an element's messages, given as `t-name="value"` attributes. Their values may
+/// contain `{{ path }}` interpolations.
+fn translation_args(element: &HtmlElement) -> Vec<(String, String)> {
+	element
+		.attributes
+		.iter()
+		.filter_map(|(name, value)| Some((name.strip_prefix("t-")?.to_owned(), value.clone())))
+		.collect()
+}
+
+fn translate(
+	world: &World,
+	entity: Entity,
+	key: &str,
+	args: &[(String, String)],
+) -> Option<String> {
+	let locale = world.get_resource::<HtmlLocale>()?;
+	let assets = world.get_resource::<Assets<HtmlTranslations>>()?;
+	let args: Vec<(String, String)> = args
+		.iter()
+		.map(|(name, template)| (name.clone(), interpolate_for(world, entity, template)))
+		.collect();
+	locale.translate(assets, key, &args)
+}
+
+/// Returns `element` with each `t:attribute="key"` replaced by `attribute` set to the message
+/// `key`, or `None` if it has no such attributes.
+///
+/// The translated attributes are also set on the [`AttributeList`] of `entity`, so that stylesheets
+/// and systems can read those that no handler uses, such as `title`.
+pub(crate) fn translate_attributes(
+	world: &mut World,
+	entity: Entity,
+	element: &HtmlElement,
+) -> Option<HtmlElement> {
+	let args = translation_args(element);
+	let attributes: Vec<TranslatedAttribute> = element
+		.attributes
+		.iter()
+		.filter_map(|(name, key)| {
+			Some(TranslatedAttribute {
+				name: name.strip_prefix("t:")?.to_owned(),
+				key: key.clone(),
+				value: translate(world, entity, key, &args).unwrap_or_else(|| key.clone()),
+			})
+		})
+		.collect();
+	if attributes.is_empty() {
+		return None;
+	}
+
+	let mut translated = element.clone();
+	translated
+		.attributes
+		.retain(|(name, _)| !name.starts_with("t:"));
+	for attribute in &attributes {
+		match translated
+			.attributes
+			.iter_mut()
+			.find(|(other, _)| *other == attribute.name)
+		{
+			Some((_, existing)) => existing.clone_from(&attribute.value),
+			None => translated
+				.attributes
+				.push((attribute.name.clone(), attribute.value.clone())),
+		}
+	}
+
+	let mut entity = world.entity_mut(entity);
+	for attribute in &attributes {
+		set_attribute(&mut entity, &attribute.name, &attribute.value);
+	}
+	entity.insert(HtmlTranslatedAttributes { attributes, args });
+	Some(translated)
+}
+
+fn set_attribute(entity: &mut EntityWorldMut, name: &str, value: &str) {
+	let (name, value) = (name.to_owned(), value.to_owned());
+	if let Some(mut attributes) = entity.get_mut::<AttributeList>() {
+		attributes.set_attribute(name, value);
+	} else {
+		entity.insert(AttributeList::from_iter([(name, value)]));
+	}
+}
+
+/// Re-translates the `t:attribute`s of `entity`. Besides its [`AttributeList`], the placeholder of
+/// an `<input>` is updated, as is its value unless it was edited.
+fn update_translated_attributes(world: &mut World, entity: Entity) {
+	let Some(translated) = world.get::<HtmlTranslatedAttributes>(entity) else {
+		return;
+	};
+	let values: Vec<String> = translated
+		.attributes
+		.iter()
+		.map(|attribute| {
+			translate(world, entity, &attribute.key, &translated.args)
+				.unwrap_or_else(|| attribute.key.clone())
+		})
+		.collect();
+
+	let mut entity_mut = world.entity_mut(entity);
+	let Some(mut translated) = entity_mut.take::<HtmlTranslatedAttributes>() else {
+		return;
+	};
+	for (attribute, value) in translated.attributes.iter_mut().zip(values) {
+		if attribute.value == value {
+			continue;
+		}
+		set_attribute(&mut entity_mut, &attribute.name, &value);
+		match attribute.name.as_str() {
+			"placeholder" => {
+				entity_mut.world_scope(|world| set_input_placeholder(world, entity, &value));
+			}
+			"value" => {
+				if let Some(mut input) = entity_mut.get_mut::<HtmlTextInput>()
+					&& input.value() == attribute.value
+				{
+					input.set_value(value.clone());
+				}
+			}
+			_ => {}
+		}
+		attribute.value = value;
+	}
+	entity_mut.insert(translated);
+}
+
+/// Sets the text of `entity` to the message of the `t` attribute of `element`, keeping the text of
+/// `element` as the fallback. Returns whether it has one, in which case the text children of
+/// `element` are not spawned.
+///
+/// Like other text, the message goes into a run of its own next to node children, such as the box
+/// of a `<checkbox>`.
+pub(crate) fn insert_translated_text(
+	world: &mut World,
+	entity: Entity,
+	element: &HtmlElement,
+) -> bool {
+	let Some(key) = element.attribute("t") else {
+		return false;
+	};
+	let fallback = element
+		.children
+		.iter()
+		.filter_map(|child| match child {
+			HtmlNode::Text(text) => Some(text.as_str()),
+			HtmlNode::Element(_) => None,
+		})
+		.collect::<String>()
+		.trim()
+		.to_owned();
+	let entity = if needs_text_run(world, entity) {
+		text_run(world, entity, Entity::PLACEHOLDER)
+	} else {
+		entity
+	};
+	world.entity_mut(entity).insert(HtmlTranslatedText {
+		key: key.to_owned(),
+		args: translation_args(element),
+		fallback,
+	});
+	update_translated_text(world, entity);
+	true
+}
+
+fn update_translated_text(world: &mut World, entity: Entity) {
+	let Some(translated) = world.get::<HtmlTranslatedText>(entity) else {
+		return;
+	};
+	let value = translate(world, entity, &translated.key, &translated.args).unwrap_or_else(|| {
+		if translated.fallback.is_empty() {
+			translated.key.clone()
+		} else {
+			interpolate_for(world, entity, &translated.fallback)
+		}
+	});
+
+	let mut entity = world.entity_mut(entity);
+	if let Some(mut span) = entity.get_mut::<TextSpan>() {
+		if span.0 != value {
+			span.0 = value;
+		}
+	} else if let Some(mut text) = entity.get_mut::<Text>() {
+		if text.0 != value {
+			text.0 = value;
+		}
+	} else {
+		entity.insert(Text::new(value));
+	}
+}
+
+/// Re-translates every `t="key"` text and `t:attribute` in place.
+pub(crate) fn html_locale_update(world: &mut World) {
+	let mut q_texts = world.query_filtered::<Entity, With<HtmlTranslatedText>>();
+	let texts: Vec<Entity> = q_texts.iter(world).collect();
+	for entity in texts {
+		update_translated_text(world, entity);
+	}
+
+	let mut q_attributes = world.query_filtered::<Entity, With<HtmlTranslatedAttributes>>();
+	let attributes: Vec<Entity> = q_attributes.iter(world).collect();
+	for entity in attributes {
+		update_translated_attributes(world, entity);
+	}
+}
+
+/// Whether a translation of the current language was loaded or edited since the last run.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_locale_translations_changed(
+	locale: Res<HtmlLocale>,
+	mut events: MessageReader<AssetEvent<HtmlTranslations>>,
+) -> bool {
+	let mut changed = false;
+	for event in events.read() {
+		if let AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } = event {
+			changed |= locale.is_current(*id);
+		}
+	}
+	changed
+}
+
+#[cfg(test)]
+mod locale_tests {
+	use bevy::prelude::*;
+	use bevy_flair::prelude::*;
+
+	use super::{HtmlLocale, html_locale_update};
+	use crate::{
+		build::spawn_node,
+		input::HtmlTextInput,
+		parser::parse_htmlish,
+		tags::{HtmlTagRegistry, register_builtin_tags},
+		translations::HtmlTranslations,
+	};
+
+	#[test]
+	fn test_translated_attributes() {
+		let mut app = App::new();
+		app.init_resource::<HtmlTagRegistry>()
+			.init_resource::<Assets<HtmlTranslations>>();
+		register_builtin_tags(&mut app);
+
+		let world = app.world_mut();
+		let mut assets = world.resource_mut::<Assets<HtmlTranslations>>();
+		let en = assets.add(
+			HtmlTranslations::default()
+				.with("name", "Your name")
+				.with("hint", "Type here")
+				.with("default", "Anonymous"),
+		);
+		let de = assets.add(
+			HtmlTranslations::default()
+				.with("name", "Dein Name")
+				.with("hint", "Hier tippen")
+				.with("default", "Anonym"),
+		);
+		world.insert_resource(
+			HtmlLocale::new("en")
+				.with_translations("en", en)
+				.with_translations("de", de),
+		);
+
+		let root = world.spawn(Node::default()).id();
+		let ast = parse_htmlish(
+			&r#"<input t:placeholder="name" t:title="hint" t:value="default" />"#.into(),
+		)
+		.unwrap();
+		spawn_node(world, root, &ast[0]);
+		let input = world.get::<Children>(root).unwrap()[0];
+		assert_eq!(
+			world.get::<HtmlTextInput>(input).unwrap().value(),
+			"Anonymous"
+		);
+
+		world.resource_mut::<HtmlLocale>().set_language("de");
+		html_locale_update(world);
+
+		// The input is updated in place rather than respawned.
+		assert_eq!(world.get::<Children>(root).unwrap().len(), 1);
+		assert_eq!(world.get::<HtmlTextInput>(input).unwrap().value(), "Anonym");
+		let attributes = world.get::<AttributeList>(input).unwrap();
+		assert_eq!(attributes.get_attribute("title"), Some("Hier tippen"));
+		let mut q_texts = world.query::<&Text>();
+		assert!(q_texts.iter(world).any(|text| text.0 == "Dein Name"));
+
+		// An edited value is kept.
+		world
+			.get_mut::<HtmlTextInput>(input)
+			.unwrap()
+			.set_value("Player");
+		world.resource_mut::<HtmlLocale>().set_language("en");
+		html_locale_update(world);
+		assert_eq!(world.get::<HtmlTextInput>(input).unwrap().value(), "Player");
+	}
+
+	#[test]
+	fn test_translated_text_beside_nodes() {
+		let mut app = App::new();
+		app.init_resource::<HtmlTagRegistry>()
+			.init_resource::<Assets<HtmlTranslations>>();
+		register_builtin_tags(&mut app);
+
+		let world = app.world_mut();
+		let mut assets = world.resource_mut::<Assets<HtmlTranslations>>();
+		let en = assets.add(
+			HtmlTranslations::default()
+				.with("agree", "I agree")
+				.with("jump", "Jump"),
+		);
+		let de = assets.add(
+			HtmlTranslations::default()
+				.with("agree", "Einverstanden")
+				.with("jump", "Springen"),
+		);
+		world.insert_resource(
+			HtmlLocale::new("en")
+				.with_translations("en", en)
+				.with_translations("de", de),
+		);
+
+		let root = world.spawn(Node::default()).id();
+		let ast = parse_htmlish(
+			&r#"<checkbox t="agree">Agree</checkbox><label t="jump"><key action="jump" /></label>"#
+				.into(),
+		)
+		.unwrap();
+		for node in &ast {
+			spawn_node(world, root, node);
+		}
+		world.resource_mut::<HtmlLocale>().set_language("de");
+		html_locale_update(world);
+
+		// The text goes after the box of the checkbox, and before the glyph of the key.
+		let children = world.get::<Children>(root).unwrap().to_vec();
+		for (parent, text, position) in [
+			(children[0], "Einverstanden", 1),
+			(children[1], "Springen", 0),
+		] {
+			assert!(!world.entity(parent).contains::<Text>());
+			let runs = world.get::<Children>(parent).unwrap();
+			assert_eq!(runs.len(), 2);
+			assert_eq!(world.get::<Text>(runs[position]).unwrap().0, text);
+		}
+	}
+}
@@ -1,7 +1,13 @@
-use bevy::prelude::Val;
+use bevy::{
+	prelude::Val,
+	ui::{
+		GridPlacement, GridTrack, GridTrackRepetition, MaxTrackSizingFunction,
+		MinTrackSizingFunction, RepeatedGridTrack,
+	},
+};
 
 use crate::{
-	ast::{HtmlElement, HtmlNode, HtmlTag},
+	ast::{HtmlEach, HtmlElement, HtmlNode, HtmlTag},
 	error::HtmlUiError,
 };
 
@@ -26,35 +32,20 @@ pub fn parse_htmlish(source: &String) -> Result<Vec<HtmlNode>, HtmlUiError> {
 				let end = find_byte(bytes, b'>', i)?;
 				let tag_name = &source[i + 2..end].trim();
 
-				let HtmlElement {
-					tag,
-					name_id,
-					classes,
-					gap,
-					autofocus,
-					callback,
-					children,
-				} = stack
+				let mut element = stack
 					.pop()
 					.ok_or_else(|| HtmlUiError::ParseError("unmatched closing tag".into()))?;
+				trim_text(&mut element.children);
 
-				if tag.as_str() != *tag_name {
+				if element.tag.as_str() != *tag_name {
 					return Err(HtmlUiError::ParseError(format!(
 						"expected </{}> but found </{}>",
-						tag.as_str(),
+						element.tag.as_str(),
 						tag_name
 					)));
 				}
 
-				let node = HtmlNode::Element(HtmlElement {
-					tag,
-					name_id,
-					classes,
-					gap,
-					autofocus,
-					callback,
-					children,
-				});
+				let node = HtmlNode::Element(element);
 
 				if let Some(parent) = stack.last_mut() {
 					parent.children.push(node);
@@ -67,9 +58,28 @@ pub fn parse_htmlish(source: &String) -> Result<Vec<HtmlNode>, HtmlUiError> {
 				// Opening tag
 				let end = find_byte(bytes, b'>', i)?;
 				let tag_src = &source[i + 1..end];
-				let (element, self_closing) = parse_tag(tag_src)?;
+				let (mut element, self_closing) = parse_tag(tag_src)?;
+
+				// The contents of a `<style>` are CSS, kept as a single text node up to `</style>`.
+				if element.tag == HtmlTag::Style && !self_closing {
+					let close = find_bytes(bytes, b"</style>", end + 1)
+						.ok_or_else(|| HtmlUiError::ParseError("unclosed <style> tag".into()))?;
+					let css = source[end + 1..close].trim();
+					if !css.is_empty() {
+						element.children.push(HtmlNode::Text(css.to_owned()));
+					}
+					let node = HtmlNode::Element(element);
+					if let Some(parent) = stack.last_mut() {
+						parent.children.push(node);
+					} else {
+						nodes.push(node);
+					}
+					i = close + b"</style>".len();
+					continue;
+				}
 
-				if self_closing {
+				// `<link>` is a void element, as in HTML.
+				if self_closing || element.tag == HtmlTag::Link {
 					let node = HtmlNode::Element(element);
 
 					if let Some(parent) = stack.last_mut() {
@@ -86,7 +96,8 @@ pub fn parse_htmlish(source: &String) -> Result<Vec<HtmlNode>, HtmlUiError> {
 		} else {
 			// Text node
 			let end = find_byte(bytes, b'<', i).unwrap_or(bytes.len());
-			let text = source[i..end].trim();
+			// Trimmed by `trim_text` once the siblings are known.
+			let text = &source[i..end];
 
 			if !text.is_empty() {
 				let text_node = HtmlNode::Text(text.to_string());
@@ -105,17 +116,62 @@ pub fn parse_htmlish(source: &String) -> Result<Vec<HtmlNode>, HtmlUiError> {
 		return Err(HtmlUiError::ParseError("unclosed tag".into()));
 	}
 
+	trim_text(&mut nodes);
 	Ok(nodes)
 }
 
+/// Whether `node` is a run of text like `<b>` or an icon laid out within text, next to which
+/// whitespace is kept.
+fn is_inline(node: &HtmlNode) -> bool {
+	matches!(
+		node,
+		HtmlNode::Element(element)
+			if matches!(element.tag.as_str(), "span" | "b" | "i" | "color" | "icon" | "key")
+	)
+}
+
+/// Trims the text nodes among `nodes`, dropping the empty ones. Whitespace next to an inline
+/// element is kept as a single space, so that `Press <b>A</b> to jump` keeps its spaces.
+fn trim_text(nodes: &mut Vec<HtmlNode>) {
+	let inline: Vec<bool> = nodes.iter().map(is_inline).collect();
+	let mut index = 0;
+	nodes.retain_mut(|node| {
+		let before = index > 0 && inline[index - 1];
+		let after = inline.get(index + 1).copied().unwrap_or(false);
+		index += 1;
+
+		let HtmlNode::Text(text) = node else {
+			return true;
+		};
+		let trimmed = text.trim();
+		if trimmed.is_empty() {
+			if before && after {
+				text.clear();
+				text.push(' ');
+				return true;
+			}
+			return false;
+		}
+		let leading = before && text.starts_with(char::is_whitespace);
+		let trailing = after && text.ends_with(char::is_whitespace);
+		*text = format!(
+			"{}{trimmed}{}",
+			if leading { " " } else { "" },
+			if trailing { " " } else { "" }
+		);
+		true
+	});
+}
+
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod parse_htmlish_tests {
 	use bevy::ui::Val;
 
-	use crate::ast::HtmlElement;
+	use crate::ast::{HtmlEach, HtmlElement};
 
 	use super::super::ast::{HtmlNode, HtmlTag};
-	use super::parse_htmlish;
+	use super::{parse_grid_placement, parse_grid_tracks, parse_htmlish};
 
 	const GOOD_HTML: &str = r#"
 		<ui class="a">
@@ -143,17 +199,21 @@ mod parse_htmlish_tests {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children,
 		}) = &good[0]
 		else {
 			panic!("<ui> is not Element");
 		};
-		assert_eq!(*tag, HtmlTag::Ui, "<ui> tag was not <ui>.");
+		assert_eq!(*tag, HtmlTag::Named("ui".into()), "<ui> tag was not <ui>.");
 		assert!(name_id.is_none(), "<ui> had non-existent id field.");
 		assert_eq!(*classes, vec!["a".to_owned()]);
 		assert_eq!(*gap, Val::Auto);
 		assert!(!autofocus, "<ui> had non-existent autofocus.");
 		assert!(callback.is_none(), "<ui> had non-existent callback.");
+		assert!(each.is_none(), "<ui> had non-existent for.");
+		assert!(attributes.is_empty(), "<ui> had non-existent attributes.");
 		assert_eq!(children.len(), 1, "Wrong number of HTML <button> nodes.");
 		let HtmlNode::Element(HtmlElement {
 			tag,
@@ -162,12 +222,18 @@ mod parse_htmlish_tests {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children,
 		}) = &children[0]
 		else {
 			panic!("<button> is not Element");
 		};
-		assert_eq!(*tag, HtmlTag::Button, "<button> tag was not <button>.");
+		assert_eq!(
+			*tag,
+			HtmlTag::Named("button".into()),
+			"<button> tag was not <button>."
+		);
 		assert!(
 			*name_id == Some("my-button".to_owned()),
 			"<button> lacked id field."
@@ -176,6 +242,11 @@ mod parse_htmlish_tests {
 		assert_eq!(*gap, Val::Auto);
 		assert!(autofocus, "<button> lacked autofocus.");
 		assert!(callback.is_none(), "<button> had non-existent callback.");
+		assert!(each.is_none(), "<button> had non-existent for.");
+		assert!(
+			attributes.is_empty(),
+			"<button> had non-existent attributes."
+		);
 		assert_eq!(children.len(), 1, "Wrong number of HTML <vbox> nodes.");
 		let HtmlNode::Element(HtmlElement {
 			tag,
@@ -184,17 +255,25 @@ mod parse_htmlish_tests {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children,
 		}) = &children[0]
 		else {
 			panic!("<vbox> is not Element");
 		};
-		assert_eq!(*tag, HtmlTag::VBox, "<vbox> tag was not <vbox>.");
+		assert_eq!(
+			*tag,
+			HtmlTag::Named("vbox".into()),
+			"<vbox> tag was not <vbox>."
+		);
 		assert!(name_id.is_none(), "<vbox> had non-existent id field.");
 		assert_eq!(*classes, vec!["d".to_owned()]);
 		assert_eq!(*gap, Val::Px(12.25));
 		assert!(!autofocus, "<vbox> had non-existent autofocus.");
 		assert!(callback.is_none(), "<vbox> had non-existent callback.");
+		assert!(each.is_none(), "<vbox> had non-existent for.");
+		assert!(attributes.is_empty(), "<vbox> had non-existent attributes.");
 		assert_eq!(children.len(), 3, "Wrong number of HTML <vbox> children.");
 		let child0 = &children[0];
 		let child1 = &children[1];
@@ -206,17 +285,28 @@ mod parse_htmlish_tests {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children,
 		}) = child0
 		else {
 			panic!("<spacer> #0 is not Element");
 		};
-		assert_eq!(*tag, HtmlTag::Spacer, "<spacer> tag was not <spacer>.");
+		assert_eq!(
+			*tag,
+			HtmlTag::Named("spacer".into()),
+			"<spacer> tag was not <spacer>."
+		);
 		assert!(name_id.is_none(), "<spacer> had non-existent id field.");
 		assert_eq!(classes.len(), 0, "Wrong number of HTML <spacer> classes.");
 		assert_eq!(*gap, Val::Auto);
 		assert!(!autofocus, "<spacer> had non-existent autofocus.");
 		assert!(callback.is_none(), "<spacer> had non-existent callback.");
+		assert!(each.is_none(), "<spacer> had non-existent for.");
+		assert!(
+			attributes.is_empty(),
+			"<spacer> had non-existent attributes."
+		);
 		assert_eq!(children.len(), 0, "Wrong number of HTML <spacer> children.");
 		let HtmlNode::Element(HtmlElement {
 			tag,
@@ -225,12 +315,18 @@ mod parse_htmlish_tests {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children,
 		}) = child1
 		else {
 			panic!("<label> #1 is not Element");
 		};
-		assert_eq!(*tag, HtmlTag::Label, "<label> tag was not <label>.");
+		assert_eq!(
+			*tag,
+			HtmlTag::Named("label".into()),
+			"<label> tag was not <label>."
+		);
 		assert!(
 			*name_id == Some("my-label".to_owned()),
 			"<label> lacked id field."
@@ -239,6 +335,11 @@ mod parse_htmlish_tests {
 		assert_eq!(*gap, Val::Auto);
 		assert!(!autofocus, "<label> had non-existent autofocus.");
 		assert!(callback.is_none(), "<label> had non-existent callback.");
+		assert!(each.is_none(), "<label> had non-existent for.");
+		assert!(
+			attributes.is_empty(),
+			"<label> had non-existent attributes."
+		);
 		assert_eq!(children.len(), 1, "Wrong number of HTML <label> children.");
 		let HtmlNode::Text(text) = &children[0] else {
 			panic!("<label> text is not Text");
@@ -251,19 +352,170 @@ mod parse_htmlish_tests {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children,
 		}) = child2
 		else {
 			panic!("<spacer> #2 is not Element");
 		};
-		assert_eq!(*tag, HtmlTag::Spacer, "<spacer> tag was not <spacer>.");
+		assert_eq!(
+			*tag,
+			HtmlTag::Named("spacer".into()),
+			"<spacer> tag was not <spacer>."
+		);
 		assert!(name_id.is_none(), "<spacer> had non-existent id field.");
 		assert_eq!(classes.len(), 0, "Wrong number of HTML <spacer> classes.");
 		assert_eq!(*gap, Val::Auto);
 		assert!(!autofocus, "<spacer> had non-existent autofocus.");
 		assert!(callback.is_none(), "<spacer> had non-existent callback.");
+		assert!(each.is_none(), "<spacer> had non-existent for.");
+		assert!(
+			attributes.is_empty(),
+			"<spacer> had non-existent attributes."
+		);
 		assert_eq!(children.len(), 0, "Wrong number of HTML <spacer> children.");
 	}
+
+	#[test]
+	fn test_for() {
+		let parsed = parse_htmlish(
+			&r#"<hbox for="item in inventory.items" key="item.id"><label>{{ item.name }}</label></hbox>"#
+				.into(),
+		)
+		.expect("for loop failed to parse");
+		let HtmlNode::Element(HtmlElement { each, children, .. }) = &parsed[0] else {
+			panic!("<hbox> is not Element");
+		};
+		assert_eq!(
+			*each,
+			Some(HtmlEach {
+				binding: "item".to_owned(),
+				collection: "inventory.items".to_owned(),
+				key: Some("item.id".to_owned()),
+			})
+		);
+		let HtmlNode::Element(HtmlElement { children, .. }) = &children[0] else {
+			panic!("<label> is not Element");
+		};
+		let HtmlNode::Text(text) = &children[0] else {
+			panic!("<label> text is not Text");
+		};
+		assert_eq!(text, "{{ item.name }}");
+
+		assert!(
+			parse_htmlish(&r#"<hbox for="inventory.items"></hbox>"#.into()).is_err(),
+			"Malformed for attribute was accepted."
+		);
+	}
+
+	#[test]
+	fn test_spans() {
+		let parsed = parse_htmlish(
+			&"<label>\n\tPress <b>A</b> <color value=\"red\">now</color> to jump\n</label>".into(),
+		)
+		.expect("spans failed to parse");
+		let HtmlNode::Element(label) = &parsed[0] else {
+			panic!("<label> is not Element");
+		};
+		let texts: Vec<&str> = label
+			.children
+			.iter()
+			.map(|child| match child {
+				HtmlNode::Text(text) => text.as_str(),
+				HtmlNode::Element(element) => element.tag.as_str(),
+			})
+			.collect();
+		assert_eq!(texts, vec!["Press ", "b", " ", "color", " to jump"]);
+	}
+
+	#[test]
+	fn test_style() {
+		let parsed = parse_htmlish(
+			&"<ui><style>\n.a > .b { color: red; }\n</style><label>Hi</label></ui>".into(),
+		)
+		.expect("style failed to parse");
+		let HtmlNode::Element(ui) = &parsed[0] else {
+			panic!("<ui> is not Element");
+		};
+		let HtmlNode::Element(style) = &ui.children[0] else {
+			panic!("<style> is not Element");
+		};
+		assert_eq!(style.tag, HtmlTag::Style);
+		let [HtmlNode::Text(css)] = style.children.as_slice() else {
+			panic!("<style> does not contain a single text node");
+		};
+		assert_eq!(css, ".a > .b { color: red; }");
+		assert_eq!(ui.children.len(), 2);
+	}
+
+	#[test]
+	fn test_attributes() {
+		let parsed = parse_htmlish(&r#"<include src="widgets/header.html" hidden />"#.into())
+			.expect("include failed to parse");
+		let HtmlNode::Element(element) = &parsed[0] else {
+			panic!("<include> is not Element");
+		};
+		assert_eq!(element.tag, HtmlTag::Include);
+		assert_eq!(element.attribute("src"), Some("widgets/header.html"));
+		assert_eq!(element.attribute("hidden"), Some(""));
+		assert!(element.attribute("missing").is_none());
+
+		let parsed = parse_htmlish(&r#"<label style="color: red; padding: 4px">Hi</label>"#.into())
+			.expect("label failed to parse");
+		let HtmlNode::Element(element) = &parsed[0] else {
+			panic!("<label> is not Element");
+		};
+		assert_eq!(element.attribute("style"), Some("color: red; padding: 4px"));
+	}
+
+	#[test]
+	fn test_grid() {
+		use bevy::ui::{
+			GridPlacement, GridTrack, GridTrackRepetition, MaxTrackSizingFunction,
+			MinTrackSizingFunction, RepeatedGridTrack,
+		};
+
+		assert_eq!(
+			parse_grid_tracks("repeat(4, 1fr)").unwrap(),
+			vec![RepeatedGridTrack::repeat_many(
+				GridTrackRepetition::Count(4),
+				vec![GridTrack::fr(1.0)],
+			)]
+		);
+		assert_eq!(
+			parse_grid_tracks("auto minmax(100px, 1fr)  25%").unwrap(),
+			vec![
+				GridTrack::auto(),
+				GridTrack::minmax(
+					MinTrackSizingFunction::Px(100.0),
+					MaxTrackSizingFunction::Fraction(1.0),
+				),
+				GridTrack::percent(25.0),
+			]
+		);
+		assert!(parse_grid_tracks("repeat(0, 1fr)").is_err());
+		assert!(parse_grid_tracks("minmax(1fr, 2fr)").is_err());
+
+		assert_eq!(
+			parse_grid_placement("2 / span 3").unwrap(),
+			GridPlacement::start_span(2, 3)
+		);
+		assert_eq!(
+			parse_grid_placement("span 2").unwrap(),
+			GridPlacement::span(2)
+		);
+		assert_eq!(
+			parse_grid_placement("1 / -1").unwrap(),
+			GridPlacement::start_end(1, -1)
+		);
+		assert_eq!(
+			parse_grid_placement("auto / span 3").unwrap(),
+			GridPlacement::span(3)
+		);
+		assert!(parse_grid_placement("0").is_err());
+		assert!(parse_grid_placement("span 2 / span 3").is_err());
+	}
 }
 
 fn parse_tag(src: &str) -> Result<(HtmlElement, bool), HtmlUiError> {
@@ -279,13 +531,16 @@ fn parse_tag(src: &str) -> Result<(HtmlElement, bool), HtmlUiError> {
 		.next()
 		.ok_or_else(|| HtmlUiError::ParseError("empty tag".into()))?;
 
-	let tag = HtmlTag::from_str(tag_name)?;
+	let tag = tag_name.parse::<HtmlTag>()?;
 
 	let mut name_id = None;
 	let mut classes = Vec::new();
 	let mut gap: Val = Val::Auto;
 	let mut autofocus: bool = false;
 	let mut callback = None;
+	let mut for_each = None;
+	let mut key = None;
+	let mut attributes = Vec::new();
 
 	for part in parts {
 		if let Some(rest) = part.strip_prefix("id=\"") {
@@ -303,9 +558,19 @@ fn parse_tag(src: &str) -> Result<(HtmlElement, bool), HtmlUiError> {
 			autofocus = true;
 		} else if let Some(rest) = part.strip_prefix("callback=\"") {
 			callback = Some(rest.trim_end_matches('"').to_owned());
+		} else if let Some(rest) = part.strip_prefix("for=\"") {
+			for_each = Some(rest.trim_end_matches('"'));
+		} else if let Some(rest) = part.strip_prefix("key=\"") {
+			key = Some(rest.trim_end_matches('"').trim().to_owned());
+		} else if let Some((name, value)) = part.split_once('=') {
+			attributes.push((name.to_owned(), value.trim_matches('"').to_owned()));
+		} else {
+			attributes.push((part.to_owned(), String::new()));
 		}
 	}
 
+	let each = for_each.map(|value| parse_each(value, key)).transpose()?;
+
 	Ok((
 		HtmlElement {
 			tag,
@@ -314,12 +579,35 @@ fn parse_tag(src: &str) -> Result<(HtmlElement, bool), HtmlUiError> {
 			gap,
 			autofocus,
 			callback,
+			each,
+			attributes,
 			children: Vec::new(),
 		},
 		self_closing,
 	))
 }
 
+/// Parses `for="item in path.to.list"`. Inside a `<template>`, the list can also be a param such as
+/// `{{ list }}`, which is substituted when the template is used.
+fn parse_each(value: &str, key: Option<String>) -> Result<HtmlEach, HtmlUiError> {
+	let mut words = value.split_whitespace();
+	let (binding, in_keyword) = (words.next(), words.next());
+	let collection = words.collect::<Vec<_>>().join(" ");
+	let is_path = !collection.is_empty() && !collection.contains(' ');
+	let is_param = collection.starts_with("{{") && collection.ends_with("}}");
+	let (Some(binding), Some("in"), true) = (binding, in_keyword, is_path || is_param) else {
+		return Err(HtmlUiError::ParseError(format!(
+			"invalid for attribute `{value}`, expected `item in path.to.list`"
+		)));
+	};
+
+	Ok(HtmlEach {
+		binding: binding.to_owned(),
+		collection,
+		key,
+	})
+}
+
 fn split_quoted_whitespace(s: &str) -> Vec<&str> {
 	let mut parts = Vec::new();
 	let mut start = 0;
@@ -345,40 +633,255 @@ fn split_quoted_whitespace(s: &str) -> Vec<&str> {
 	parts
 }
 
-fn parse_val(string: &str) -> Result<Val, HtmlUiError> {
+/// Parses a length such as `12px`, `50%`, `10vw`, `auto` or a bare number of pixels.
+pub(crate) fn parse_val(string: &str) -> Result<Val, HtmlUiError> {
 	if let Some(pc) = string.strip_suffix("%") {
 		Ok(Val::Percent(pc.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	} else if let Some(px) = string.strip_suffix("px") {
 		Ok(Val::Px(px.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	} else if let Some(vmax) = string.strip_suffix("vmax") {
 		Ok(Val::VMax(vmax.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	} else if let Some(vmin) = string.strip_suffix("vmin") {
 		Ok(Val::VMin(vmin.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	} else if let Some(vw) = string.strip_suffix("vw") {
 		Ok(Val::Vw(vw.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	} else if let Some(vh) = string.strip_suffix("vh") {
 		Ok(Val::Vh(vh.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	} else if string == "auto" {
 		Ok(Val::Auto)
 	} else {
 		Ok(Val::Px(string.parse::<f32>().map_err(|err| {
-			HtmlUiError::ParseError(format!("invalid gap tag: {err}"))
+			HtmlUiError::ParseError(format!("invalid length `{string}`: {err}"))
 		})?))
 	}
 }
 
+/// Parses a grid track list such as `repeat(4, 1fr)`, `auto 1fr` or `minmax(100px, 1fr) 200px`,
+/// like CSS `grid-template-columns` and `grid-template-rows`.
+pub(crate) fn parse_grid_tracks(string: &str) -> Result<Vec<RepeatedGridTrack>, HtmlUiError> {
+	split_grid_list(string)
+		.into_iter()
+		.map(|item| {
+			let Some(args) = grid_function(item, "repeat") else {
+				return parse_grid_track(item).map(RepeatedGridTrack::from);
+			};
+			let (count, tracks) = args.split_once(',').ok_or_else(|| {
+				HtmlUiError::ParseError(format!(
+					"invalid grid repeat `{item}`, expected `repeat(count, tracks)`"
+				))
+			})?;
+			let repetition = match count.trim() {
+				"auto-fill" => GridTrackRepetition::AutoFill,
+				"auto-fit" => GridTrackRepetition::AutoFit,
+				count => match count.parse::<u16>() {
+					Ok(count) if count > 0 => GridTrackRepetition::Count(count),
+					_ => {
+						return Err(HtmlUiError::ParseError(format!(
+							"invalid grid repeat count `{count}`"
+						)));
+					}
+				},
+			};
+			let tracks = split_grid_list(tracks)
+				.into_iter()
+				.map(parse_grid_track)
+				.collect::<Result<Vec<_>, _>>()?;
+			Ok(RepeatedGridTrack::repeat_many(repetition, tracks))
+		})
+		.collect()
+}
+
+/// Parses one grid track: a size, `minmax(min, max)` or `fit-content(limit)`.
+fn parse_grid_track(string: &str) -> Result<GridTrack, HtmlUiError> {
+	if let Some(args) = grid_function(string, "minmax") {
+		let (min, max) = args.split_once(',').ok_or_else(|| {
+			HtmlUiError::ParseError(format!(
+				"invalid grid track `{string}`, expected `minmax(min, max)`"
+			))
+		})?;
+		let (min, _) = parse_grid_size(min.trim())?;
+		let (_, max) = parse_grid_size(max.trim())?;
+		let min = min.ok_or_else(|| {
+			HtmlUiError::ParseError(format!(
+				"invalid grid track `{string}`, the minimum cannot be `fr`"
+			))
+		})?;
+		return Ok(GridTrack::minmax(min, max));
+	}
+	if let Some(limit) = grid_function(string, "fit-content") {
+		return match parse_val(limit.trim())? {
+			Val::Px(px) => Ok(GridTrack::fit_content_px(px)),
+			Val::Percent(percent) => Ok(GridTrack::fit_content_percent(percent)),
+			_ => Err(HtmlUiError::ParseError(format!(
+				"invalid grid track `{string}`, fit-content takes px or %"
+			))),
+		};
+	}
+	let (min, max) = parse_grid_size(string)?;
+	Ok(GridTrack::minmax(
+		min.unwrap_or(MinTrackSizingFunction::Auto),
+		max,
+	))
+}
+
+/// The sizing functions of a single grid track size. A flexible `fr` size has no minimum of its own.
+fn parse_grid_size(
+	string: &str,
+) -> Result<(Option<MinTrackSizingFunction>, MaxTrackSizingFunction), HtmlUiError> {
+	Ok(match string {
+		"auto" => (
+			Some(MinTrackSizingFunction::Auto),
+			MaxTrackSizingFunction::Auto,
+		),
+		"min-content" => (
+			Some(MinTrackSizingFunction::MinContent),
+			MaxTrackSizingFunction::MinContent,
+		),
+		"max-content" => (
+			Some(MinTrackSizingFunction::MaxContent),
+			MaxTrackSizingFunction::MaxContent,
+		),
+		_ => {
+			if let Some(fr) = string.strip_suffix("fr") {
+				let fr = fr.parse::<f32>().map_err(|err| {
+					HtmlUiError::ParseError(format!("invalid grid track `{string}`: {err}"))
+				})?;
+				return Ok((None, MaxTrackSizingFunction::Fraction(fr)));
+			}
+			match parse_val(string)? {
+				Val::Px(px) => (
+					Some(MinTrackSizingFunction::Px(px)),
+					MaxTrackSizingFunction::Px(px),
+				),
+				Val::Percent(percent) => (
+					Some(MinTrackSizingFunction::Percent(percent)),
+					MaxTrackSizingFunction::Percent(percent),
+				),
+				Val::Vw(vw) => (
+					Some(MinTrackSizingFunction::Vw(vw)),
+					MaxTrackSizingFunction::Vw(vw),
+				),
+				Val::Vh(vh) => (
+					Some(MinTrackSizingFunction::Vh(vh)),
+					MaxTrackSizingFunction::Vh(vh),
+				),
+				Val::VMin(vmin) => (
+					Some(MinTrackSizingFunction::VMin(vmin)),
+					MaxTrackSizingFunction::VMin(vmin),
+				),
+				Val::VMax(vmax) => (
+					Some(MinTrackSizingFunction::VMax(vmax)),
+					MaxTrackSizingFunction::VMax(vmax),
+				),
+				Val::Auto => (
+					Some(MinTrackSizingFunction::Auto),
+					MaxTrackSizingFunction::Auto,
+				),
+			}
+		}
+	})
+}
+
+/// Parses a grid item placement like CSS `grid-column` and `grid-row`: `2`, `span 2`, `1 / 3`,
+/// `2 / span 2` or `auto`. A span at both ends, such as `span 2 / span 3`, is an error rather than
+/// quietly dropping the second span as CSS does.
+pub(crate) fn parse_grid_placement(string: &str) -> Result<GridPlacement, HtmlUiError> {
+	enum Line {
+		Auto,
+		At(i16),
+		Span(u16),
+	}
+
+	let parse_line = |line: &str| -> Result<Line, HtmlUiError> {
+		let line = line.trim();
+		let invalid = || {
+			HtmlUiError::ParseError(format!(
+				"invalid grid placement `{string}`, expected `start`, `span count` or `start / end`"
+			))
+		};
+		if line == "auto" {
+			Ok(Line::Auto)
+		} else if let Some(span) = line.strip_prefix("span") {
+			match span.trim().parse::<u16>() {
+				Ok(span) if span > 0 => Ok(Line::Span(span)),
+				_ => Err(invalid()),
+			}
+		} else {
+			match line.parse::<i16>() {
+				Ok(at) if at != 0 => Ok(Line::At(at)),
+				_ => Err(invalid()),
+			}
+		}
+	};
+
+	let (start, end) = match string.split_once('/') {
+		Some((start, end)) => (parse_line(start)?, parse_line(end)?),
+		None => (parse_line(string)?, Line::Auto),
+	};
+	Ok(match (start, end) {
+		(Line::Auto, Line::Auto) => GridPlacement::auto(),
+		(Line::At(start), Line::Auto) => GridPlacement::start(start),
+		(Line::At(start), Line::At(end)) => GridPlacement::start_end(start, end),
+		(Line::At(start), Line::Span(span)) => GridPlacement::start_span(start, span),
+		(Line::Auto, Line::At(end)) => GridPlacement::end(end),
+		(Line::Span(span), Line::At(end)) => GridPlacement::end_span(end, span),
+		(Line::Span(span), Line::Auto) | (Line::Auto, Line::Span(span)) => {
+			GridPlacement::span(span)
+		}
+		(Line::Span(_), Line::Span(_)) => {
+			return Err(HtmlUiError::ParseError(format!(
+				"invalid grid placement `{string}`, only one of its start and end can be a span"
+			)));
+		}
+	})
+}
+
+/// The arguments of `name(...)`, if `string` is a call to it.
+fn grid_function<'a>(string: &'a str, name: &str) -> Option<&'a str> {
+	string
+		.strip_prefix(name)?
+		.trim_start()
+		.strip_prefix('(')?
+		.strip_suffix(')')
+}
+
+/// Splits `string` on whitespace outside of parentheses.
+fn split_grid_list(string: &str) -> Vec<&str> {
+	let mut items = Vec::new();
+	let mut depth = 0_usize;
+	let mut start = None;
+	for (i, c) in string.char_indices() {
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.saturating_sub(1),
+			c if c.is_whitespace() && depth == 0 => {
+				if let Some(begin) = start.take() {
+					items.push(&string[begin..i]);
+				}
+				continue;
+			}
+			_ => {}
+		}
+		start.get_or_insert(i);
+	}
+	if let Some(begin) = start {
+		items.push(&string[begin..]);
+	}
+	items
+}
+
 fn find_byte(bytes: &[u8], needle: u8, start: usize) -> Result<usize, HtmlUiError> {
 	bytes[start..]
 		.iter()
@@ -387,6 +890,13 @@ fn find_byte(bytes: &[u8], needle: u8, start: usize) -> Result<usize, HtmlUiErro
 		.ok_or_else(|| HtmlUiError::ParseError("unexpected end of input".into()))
 }
 
+fn find_bytes(bytes: &[u8], needle: &[u8], start: usize) -> Option<usize> {
+	bytes[start..]
+		.windows(needle.len())
+		.position(|window| window == needle)
+		.map(|p| start + p)
+}
+
 fn starts_with(bytes: &[u8], i: usize, s: &[u8]) -> bool {
 	bytes.get(i..i + s.len()) == Some(s)
 }
@@ -1,28 +1,214 @@
 use bevy::{
 	asset::{LoadState, RecursiveDependencyLoadState},
 	ecs::message::{MessageCursor, Messages},
+	input::keyboard::KeyboardInput,
+	input_focus::InputFocus,
 	prelude::*,
+	ui::UiSystems,
 };
-use bevy_flair::style::StyleSheet;
+use bevy_flair::style::{StyleSheet, StyleSystems};
 
 use crate::{
 	asset::HtmlUiAsset,
+	bindings::html_ui_update_bindings,
 	build::{HtmlUiRoot, spawn_html_ui},
+	checkbox::{html_checkbox_click, html_checkbox_confirm, html_checkbox_update_display},
+	data::HtmlUiData,
+	direction::{
+		HtmlDirection, HtmlRestyled, html_direction_mirror_styles, html_direction_unmirror_styles,
+		html_direction_update,
+	},
+	form::{HtmlValueChanged, HtmlValueSubmitted},
+	icon::{HtmlInputDevice, HtmlKeyGlyphs, html_input_device_detect, html_key_update_glyphs},
+	input::{
+		HtmlUiClipboard, html_input_drag, html_input_keyboard, html_input_press,
+		html_input_update_display,
+	},
 	loader::HtmlUiLoader,
+	locale::{HtmlLocale, html_locale_translations_changed, html_locale_update},
+	progress::{html_progress_update_bindings, html_progress_update_display},
+	radio::{html_radio_click, html_radio_keyboard, html_radio_sync},
 	resources::HtmlCssUiResource,
+	scroll::{
+		html_scroll_drag, html_scroll_focus_into_view, html_scroll_update_scrollbars,
+		html_scroll_wheel,
+	},
+	select::{
+		html_select_click, html_select_close_outside, html_select_keyboard,
+		html_select_update_display,
+	},
+	slider::{
+		html_slider_drag, html_slider_keyboard, html_slider_press, html_slider_update_display,
+	},
+	span::html_span_bold_font,
+	tags::{HtmlTagRegistry, register_builtin_tags},
+	translations::{HtmlFluentLoader, HtmlKeyValueLoader, HtmlTranslations},
 };
 
 pub struct HtmlUiPlugin;
 
 impl Plugin for HtmlUiPlugin {
 	fn build(&self, app: &mut App) {
+		let tags = app
+			.world_mut()
+			.get_resource_or_init::<HtmlTagRegistry>()
+			.names();
 		app.init_asset::<HtmlUiAsset>()
-			.register_asset_loader(HtmlUiLoader)
+			.register_asset_loader(HtmlUiLoader::new(tags))
+			.init_resource::<HtmlUiData>()
+			.init_resource::<HtmlTagRegistry>()
+			.init_resource::<InputFocus>()
+			.add_message::<KeyboardInput>()
+			.add_message::<HtmlValueChanged>()
+			.add_message::<HtmlValueSubmitted>()
 			.add_systems(Update, html_ui_hot_reload)
-			.add_systems(Update, html_ui_watch_load);
+			.add_systems(Update, html_ui_watch_load)
+			.add_systems(
+				Update,
+				html_ui_update_bindings.run_if(resource_changed::<HtmlUiData>),
+			)
+			.add_systems(
+				PostUpdate,
+				html_span_bold_font
+					.after(StyleSystems::ApplyComputedProperties)
+					.before(UiSystems::Content),
+			);
+
+		add_locale_systems(app);
+		add_direction_systems(app);
+		add_input_systems(app);
+		add_checkbox_systems(app);
+		add_slider_systems(app);
+		add_select_systems(app);
+		add_progress_systems(app);
+		add_key_systems(app);
+		add_scroll_systems(app);
+		register_builtin_tags(app);
 	}
 }
 
+fn add_locale_systems(app: &mut App) {
+	app.init_asset::<HtmlTranslations>()
+		.register_asset_loader(HtmlFluentLoader)
+		.register_asset_loader(HtmlKeyValueLoader)
+		.init_resource::<HtmlLocale>()
+		.add_systems(
+			Update,
+			html_locale_update
+				.run_if(
+					resource_changed::<HtmlLocale>
+						.or(resource_changed::<HtmlUiData>)
+						.or(html_locale_translations_changed),
+				)
+				.after(html_ui_update_bindings),
+		);
+}
+
+fn add_direction_systems(app: &mut App) {
+	app.init_resource::<HtmlDirection>()
+		.init_resource::<HtmlRestyled>()
+		.add_systems(
+			Update,
+			html_direction_update.run_if(
+				resource_changed::<HtmlDirection>.and(not(resource_added::<HtmlDirection>)),
+			),
+		)
+		.add_systems(
+			PostUpdate,
+			(
+				html_direction_unmirror_styles
+					.after(StyleSystems::SetAnimationValues)
+					.before(StyleSystems::ApplyComputedProperties),
+				html_direction_mirror_styles
+					.after(StyleSystems::ApplyComputedProperties)
+					.before(UiSystems::Content),
+			),
+		);
+}
+
+fn add_input_systems(app: &mut App) {
+	app.init_resource::<HtmlUiClipboard>()
+		.add_systems(
+			Update,
+			(html_input_keyboard, html_input_update_display).chain(),
+		)
+		.add_observer(html_input_press)
+		.add_observer(html_input_drag);
+}
+
+/// Checkboxes, toggles and radio buttons.
+fn add_checkbox_systems(app: &mut App) {
+	app.add_systems(
+		Update,
+		(
+			html_checkbox_confirm,
+			html_radio_keyboard,
+			html_radio_sync,
+			html_checkbox_update_display,
+		)
+			.chain(),
+	)
+	.add_observer(html_checkbox_click)
+	.add_observer(html_radio_click);
+}
+
+fn add_slider_systems(app: &mut App) {
+	app.add_systems(
+		Update,
+		(html_slider_keyboard, html_slider_update_display).chain(),
+	)
+	.add_observer(html_slider_press)
+	.add_observer(html_slider_drag);
+}
+
+fn add_select_systems(app: &mut App) {
+	app.add_systems(
+		Update,
+		(
+			html_select_keyboard,
+			html_select_close_outside,
+			html_select_update_display,
+		)
+			.chain(),
+	)
+	.add_observer(html_select_click);
+}
+
+fn add_progress_systems(app: &mut App) {
+	app.add_systems(
+		Update,
+		(
+			html_progress_update_bindings
+				.run_if(resource_changed::<HtmlUiData>)
+				.after(html_ui_update_bindings),
+			html_progress_update_display,
+		)
+			.chain(),
+	);
+}
+
+/// The glyphs of `<key>`, which follow the input device last used.
+fn add_key_systems(app: &mut App) {
+	app.init_resource::<HtmlInputDevice>()
+		.init_resource::<HtmlKeyGlyphs>()
+		.add_systems(
+			Update,
+			(html_input_device_detect, html_key_update_glyphs).chain(),
+		);
+}
+
+fn add_scroll_systems(app: &mut App) {
+	app.add_systems(
+		Update,
+		(
+			html_scroll_focus_into_view.run_if(resource_changed::<InputFocus>),
+			html_scroll_update_scrollbars,
+		),
+	)
+	.add_observer(html_scroll_wheel)
+	.add_observer(html_scroll_drag);
+}
+
 fn html_ui_hot_reload(
 	world: &mut World,
 	mut cursor: Local<MessageCursor<AssetEvent<HtmlUiAsset>>>,
@@ -110,24 +296,23 @@ fn html_ui_watch_load(
 		// ---- Phase 2: Check load states ----
 		let asset_server = world.resource::<AssetServer>();
 
-		// Check HTML load state
-		let Some(load_state_html) = asset_server.get_load_state(id_html) else {
-			return;
-		};
-		match load_state_html {
-			LoadState::Loaded => {}
-			LoadState::NotLoaded | LoadState::Loading | LoadState::Failed(_) => return,
-		}
+		// Check HTML load state, unless the asset was added directly (such as with `html_ui!`)
+		if let Some(load_state_html) = asset_server.get_load_state(id_html) {
+			match load_state_html {
+				LoadState::Loaded => {}
+				LoadState::NotLoaded | LoadState::Loading | LoadState::Failed(_) => return,
+			}
 
-		// Check HTML recursive dependencies
-		let Some(recursive_load_state_html) =
-			asset_server.get_recursive_dependency_load_state(id_html)
-		else {
-			return;
-		};
-		match recursive_load_state_html {
-			RecursiveDependencyLoadState::Loaded => {}
-			_ => return,
+			// Check HTML recursive dependencies
+			let Some(recursive_load_state_html) =
+				asset_server.get_recursive_dependency_load_state(id_html)
+			else {
+				return;
+			};
+			match recursive_load_state_html {
+				RecursiveDependencyLoadState::Loaded => {}
+				_ => return,
+			}
 		}
 
 		// Check HTML asset exists
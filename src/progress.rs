@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::HtmlElement, bindings::interpolate_for, data::has_interpolation, tags::HtmlTagHandler,
+};
+
+/// The value of a `<progress>`, which fills it from `0.0` up to `max`.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct HtmlProgress {
+	pub value: f32,
+	pub max: f32,
+	/// Whether the bar fills bottom to top instead of left to right.
+	pub vertical: bool,
+}
+
+impl HtmlProgress {
+	/// How full the bar is, from `0.0` to `1.0`.
+	#[must_use]
+	pub fn fraction(&self) -> f32 {
+		if self.max > 0.0 {
+			(self.value / self.max).clamp(0.0, 1.0)
+		} else {
+			0.0
+		}
+	}
+}
+
+/// The fill of a `<progress>`, and the `value` and `max` attributes containing `{{ path }}`
+/// interpolations, re-evaluated whenever the data changes.
+#[derive(Component)]
+pub(crate) struct HtmlProgressParts {
+	fill: Entity,
+	value_binding: Option<String>,
+	max_binding: Option<String>,
+}
+
+/// `<progress value="{{ hp }}" max="100" orientation="vertical">`, a track with a fill sized to the
+/// value.
+///
+/// The element gets the class `progress-track`, and its fill the class `progress-fill`.
+#[derive(Default)]
+pub(crate) struct ProgressTag;
+
+impl HtmlTagHandler for ProgressTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let vertical = match element.attribute("orientation") {
+			None | Some("horizontal") => false,
+			Some("vertical") => true,
+			Some(other) => {
+				warn!("unknown <progress> orientation `{other}`, expected horizontal or vertical");
+				false
+			}
+		};
+		let binding = |name: &str| {
+			element
+				.attribute(name)
+				.filter(|value| has_interpolation(value))
+				.map(str::to_owned)
+		};
+		let value_binding = binding("value");
+		let max_binding = binding("max");
+		let value = element.attribute("value").map_or(0.0, |value| {
+			progress_number(entity.world(), entity.id(), value)
+		});
+		let max = element
+			.attribute("max")
+			.map_or(1.0, |max| progress_number(entity.world(), entity.id(), max));
+
+		let fill = entity.world_scope(|world| {
+			world
+				.spawn((
+					if vertical {
+						Node {
+							position_type: PositionType::Absolute,
+							bottom: Val::Px(0.0),
+							width: Val::Percent(100.0),
+							..default()
+						}
+					} else {
+						Node {
+							position_type: PositionType::Absolute,
+							left: Val::Px(0.0),
+							height: Val::Percent(100.0),
+							..default()
+						}
+					},
+					BackgroundColor(Color::srgb(0.3, 0.7, 0.4)),
+					ClassList::new("progress-fill"),
+					Pickable::IGNORE,
+				))
+				.id()
+		});
+
+		if let Some(mut classes) = entity.get_mut::<ClassList>() {
+			classes.add("progress-track");
+		} else {
+			entity.insert(ClassList::new("progress-track"));
+		}
+		entity.add_child(fill).insert((
+			if vertical {
+				Node {
+					width: Val::Px(12.0),
+					height: Val::Px(160.0),
+					..default()
+				}
+			} else {
+				Node {
+					width: Val::Px(160.0),
+					height: Val::Px(12.0),
+					..default()
+				}
+			},
+			BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+			HtmlProgress {
+				value,
+				max,
+				vertical,
+			},
+			HtmlProgressParts {
+				fill,
+				value_binding,
+				max_binding,
+			},
+		));
+	}
+
+	fn spawn_children(&self) -> bool {
+		false
+	}
+}
+
+/// Parses a `value` or `max` attribute of a `<progress>` after interpolating it for `entity`.
+/// A binding that does not resolve to a number counts as `0`.
+fn progress_number(world: &World, entity: Entity, text: &str) -> f32 {
+	if has_interpolation(text) {
+		return interpolate_for(world, entity, text)
+			.trim()
+			.parse()
+			.unwrap_or(0.0);
+	}
+	text.trim().parse().unwrap_or_else(|_| {
+		warn!("invalid <progress> number `{text}`");
+		0.0
+	})
+}
+
+/// Re-evaluates the bound `value` and `max` of each `<progress>` when the data changes.
+pub(crate) fn html_progress_update_bindings(world: &mut World) {
+	let mut q_bars = world.query::<(Entity, &HtmlProgress, &HtmlProgressParts)>();
+	let bars: Vec<(Entity, f32, f32)> = q_bars
+		.iter(world)
+		.filter(|(_, _, parts)| parts.value_binding.is_some() || parts.max_binding.is_some())
+		.map(|(entity, progress, parts)| {
+			let bound = |binding: &Option<String>, current: f32| {
+				binding
+					.as_ref()
+					.map_or(current, |template| progress_number(world, entity, template))
+			};
+			(
+				entity,
+				bound(&parts.value_binding, progress.value),
+				bound(&parts.max_binding, progress.max),
+			)
+		})
+		.collect();
+
+	for (entity, value, max) in bars {
+		if let Some(mut progress) = world.get_mut::<HtmlProgress>(entity) {
+			let vertical = progress.vertical;
+			progress.set_if_neq(HtmlProgress {
+				value,
+				max,
+				vertical,
+			});
+		}
+	}
+}
+
+/// Sizes the fill of each changed `<progress>` to match its value.
+pub(crate) fn html_progress_update_display(
+	bars: Query<(&HtmlProgress, &HtmlProgressParts), Changed<HtmlProgress>>,
+	mut nodes: Query<&mut Node>,
+) {
+	for (progress, parts) in &bars {
+		let Ok(mut fill) = nodes.get_mut(parts.fill) else {
+			continue;
+		};
+		let percent = Val::Percent(progress.fraction() * 100.0);
+		if progress.vertical {
+			fill.height = percent;
+		} else {
+			fill.width = percent;
+		}
+	}
+}
+
+#[cfg(test)]
+mod progress_tests {
+	use bevy::prelude::*;
+
+	use super::{
+		HtmlProgress, HtmlProgressParts, html_progress_update_bindings,
+		html_progress_update_display,
+	};
+	use crate::{build::build_tests::spawn_laid_out, data::HtmlUiData};
+
+	/// Spawns a single `<progress>`, returning it and its fill.
+	fn spawn_progress(source: &str) -> (App, Entity, Entity) {
+		let (mut app, root) = spawn_laid_out(source);
+		app.init_resource::<HtmlUiData>().add_systems(
+			Update,
+			(
+				html_progress_update_bindings.run_if(resource_changed::<HtmlUiData>),
+				html_progress_update_display,
+			)
+				.chain(),
+		);
+		app.update();
+		let progress = app.world().get::<Children>(root).unwrap()[0];
+		let fill = app.world().get::<HtmlProgressParts>(progress).unwrap().fill;
+		(app, progress, fill)
+	}
+
+	fn fill_size(app: &App, fill: Entity) -> Vec2 {
+		app.world().get::<ComputedNode>(fill).unwrap().size()
+	}
+
+	/// The fill covers `value / max` of the track, along its orientation.
+	#[test]
+	fn test_progress_fill() {
+		let (app, _, fill) = spawn_progress(r#"<progress value="3" max="4"></progress>"#);
+		assert_eq!(fill_size(&app, fill), Vec2::new(120.0, 12.0));
+
+		let (app, _, fill) =
+			spawn_progress(r#"<progress value="1" max="4" orientation="vertical"></progress>"#);
+		assert_eq!(fill_size(&app, fill), Vec2::new(12.0, 40.0));
+
+		let (app, _, fill) = spawn_progress(r#"<progress value="7" max="4"></progress>"#);
+		assert_eq!(fill_size(&app, fill), Vec2::new(160.0, 12.0));
+	}
+
+	/// A bound `value` follows the data.
+	#[test]
+	fn test_progress_binding() {
+		let (mut app, progress, fill) =
+			spawn_progress(r#"<progress value="{{ hp }}" max="{{ max_hp }}"></progress>"#);
+		assert_eq!(
+			app.world().get::<HtmlProgress>(progress).unwrap().value,
+			0.0
+		);
+
+		app.insert_resource(HtmlUiData::new().with("hp", 25).with("max_hp", 100));
+		app.update();
+		let bar = app.world().get::<HtmlProgress>(progress).unwrap();
+		assert_eq!((bar.value, bar.max), (25.0, 100.0));
+		assert_eq!(fill_size(&app, fill), Vec2::new(40.0, 12.0));
+
+		app.world_mut().resource_mut::<HtmlUiData>().set("hp", 50);
+		app.update();
+		assert_eq!(
+			app.world().get::<HtmlProgress>(progress).unwrap().value,
+			50.0
+		);
+		assert_eq!(fill_size(&app, fill), Vec2::new(80.0, 12.0));
+	}
+}
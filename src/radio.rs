@@ -0,0 +1,405 @@
+use bevy::{
+	input_focus::InputFocus,
+	prelude::*,
+	ui::{
+		Checkable, Checked, InteractionDisabled,
+		auto_directional_navigation::AutoDirectionalNavigation,
+	},
+};
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::HtmlElement,
+	checkbox::HtmlCheckboxParts,
+	data::HtmlValue,
+	form::{ConfirmInput, HtmlValueChanged, StepInput},
+	span::HtmlTextAfterParts,
+	tags::HtmlTagHandler,
+};
+
+/// The selection of a `<radio-group>`.
+///
+/// Setting `selected` from code checks the matching `<radio>` without sending [`HtmlValueChanged`].
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlRadioGroup {
+	/// The group's `name` attribute.
+	pub name: Option<String>,
+	/// The `value` of the checked `<radio>`, if any.
+	pub selected: Option<String>,
+}
+
+/// A `<radio value="...">` inside a [`HtmlRadioGroup`], which has bevy's [`Checked`] component
+/// while it is the group's selection.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct HtmlRadio {
+	pub value: String,
+}
+
+/// `<radio-group name="difficulty" value="easy">`, a vertical box whose `<radio>` descendants are
+/// mutually exclusive.
+#[derive(Default)]
+pub(crate) struct RadioGroupTag;
+
+impl HtmlTagHandler for RadioGroupTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		entity.insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: FlexDirection::Column,
+				row_gap: element.gap,
+				..default()
+			},
+			HtmlRadioGroup {
+				name: element.attribute("name").map(str::to_owned),
+				selected: element.attribute("value").map(str::to_owned),
+			},
+			Pickable::IGNORE,
+		));
+	}
+}
+
+/// `<radio value="easy" checked>Easy</radio>`, a circle with a dot followed by its children.
+///
+/// Drawn with the classes `radio-circle` and `radio-dot` for styling.
+#[derive(Default)]
+pub(crate) struct RadioTag;
+
+impl HtmlTagHandler for RadioTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let value = element.attribute("value").unwrap_or_default().to_owned();
+
+		let (circle, dot) = entity.world_scope(|world| {
+			let dot = world
+				.spawn((
+					Node {
+						width: Val::Px(8.0),
+						height: Val::Px(8.0),
+						border_radius: BorderRadius::MAX,
+						..default()
+					},
+					BackgroundColor(Color::WHITE),
+					ClassList::new("radio-dot"),
+					Pickable::IGNORE,
+				))
+				.id();
+			let circle = world
+				.spawn((
+					Node {
+						width: Val::Px(16.0),
+						height: Val::Px(16.0),
+						border: UiRect::all(Val::Px(2.0)),
+						border_radius: BorderRadius::MAX,
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						flex_shrink: 0.0,
+						..default()
+					},
+					BorderColor::all(Color::WHITE),
+					ClassList::new("radio-circle"),
+					Pickable::IGNORE,
+				))
+				.add_child(dot)
+				.id();
+			(circle, dot)
+		});
+
+		if element.attribute("checked").is_some() {
+			let id = entity.id();
+			entity.world_scope(|world| {
+				let mut ancestor = world.get::<ChildOf>(id).map(ChildOf::parent);
+				while let Some(current) = ancestor {
+					if let Some(mut group) = world.get_mut::<HtmlRadioGroup>(current) {
+						group.selected = Some(value.clone());
+						break;
+					}
+					ancestor = world.get::<ChildOf>(current).map(ChildOf::parent);
+				}
+			});
+		}
+
+		entity.add_child(circle).insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: FlexDirection::Row,
+				align_items: AlignItems::Center,
+				column_gap: element.gap,
+				..default()
+			},
+			HtmlRadio { value },
+			HtmlTextAfterParts,
+			Checkable,
+			HtmlCheckboxParts::Mark(dot),
+			AutoDirectionalNavigation::default(),
+			Pickable {
+				is_hoverable: true,
+				should_block_lower: true,
+			},
+		));
+		if element.attribute("disabled").is_some() {
+			entity.insert(InteractionDisabled);
+		}
+	}
+}
+
+/// Makes `value` the selection of `group`, reporting it if it changed.
+fn select(
+	group_entity: Entity,
+	group: &mut Mut<HtmlRadioGroup>,
+	value: &str,
+	changed: &mut MessageWriter<HtmlValueChanged>,
+) {
+	if group.selected.as_deref() == Some(value) {
+		return;
+	}
+	group.selected = Some(value.to_owned());
+	changed.write(HtmlValueChanged {
+		entity: group_entity,
+		value: HtmlValue::String(value.to_owned()),
+	});
+}
+
+/// Selects a clicked `<radio>` and focuses it.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_radio_click(
+	click: On<Pointer<Click>>,
+	radios: Query<(&HtmlRadio, Has<InteractionDisabled>)>,
+	parents: Query<&ChildOf>,
+	mut groups: Query<&mut HtmlRadioGroup>,
+	mut focus: ResMut<InputFocus>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	let Ok((radio, disabled)) = radios.get(click.entity) else {
+		return;
+	};
+	focus.set(click.entity);
+	if disabled {
+		return;
+	}
+	let Some(group_entity) = parents
+		.iter_ancestors(click.entity)
+		.find(|ancestor| groups.contains(*ancestor))
+	else {
+		return;
+	};
+	if let Ok(mut group) = groups.get_mut(group_entity) {
+		select(group_entity, &mut group, &radio.value, &mut changed);
+	}
+}
+
+/// Selects the focused `<radio>` when the user confirms, and moves the focus and selection to the
+/// previous or next radio in its group with the arrow keys or D-pad.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_radio_keyboard(
+	mut confirm: ConfirmInput,
+	mut step: StepInput,
+	mut focus: ResMut<InputFocus>,
+	radios: Query<(&HtmlRadio, Has<InteractionDisabled>)>,
+	children: Query<&Children>,
+	mut groups: Query<(Entity, &mut HtmlRadioGroup)>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	let confirmed = confirm.just_confirmed();
+	let step = step.just_stepped();
+	let direction = (step.x + step.y).signum();
+	if !confirmed && direction == 0 {
+		return;
+	}
+	let Some(focused) = focus.get().filter(|focused| radios.contains(*focused)) else {
+		return;
+	};
+
+	for (group_entity, mut group) in &mut groups {
+		let group_radios: Vec<Entity> = children
+			.iter_descendants_depth_first(group_entity)
+			.filter(|entity| radios.get(*entity).is_ok_and(|(_, disabled)| !disabled))
+			.collect();
+		let Some(index) = group_radios.iter().position(|radio| *radio == focused) else {
+			continue;
+		};
+
+		let target = if direction == 0 {
+			focused
+		} else {
+			let len = group_radios.len().cast_signed();
+			group_radios[(index.cast_signed() + direction as isize)
+				.rem_euclid(len)
+				.cast_unsigned()]
+		};
+		if let Ok((radio, _)) = radios.get(target) {
+			focus.set(target);
+			select(group_entity, &mut group, &radio.value, &mut changed);
+		}
+		return;
+	}
+}
+
+/// Checks the `<radio>` matching the selection of each group whose selection changed, or that has
+/// new radios.
+pub(crate) fn html_radio_sync(
+	mut commands: Commands,
+	groups: Query<(Entity, Ref<HtmlRadioGroup>)>,
+	added: Query<Entity, Added<HtmlRadio>>,
+	radios: Query<(&HtmlRadio, Has<Checked>)>,
+	parents: Query<&ChildOf>,
+	children: Query<&Children>,
+) {
+	let mut dirty: Vec<Entity> = groups
+		.iter()
+		.filter(|(_, group)| group.is_changed())
+		.map(|(entity, _)| entity)
+		.collect();
+	for radio in &added {
+		if let Some(group) = parents
+			.iter_ancestors(radio)
+			.find(|ancestor| groups.contains(*ancestor))
+			&& !dirty.contains(&group)
+		{
+			dirty.push(group);
+		}
+	}
+
+	for group_entity in dirty {
+		let Ok((_, group)) = groups.get(group_entity) else {
+			continue;
+		};
+		for entity in children.iter_descendants(group_entity) {
+			let Ok((radio, checked)) = radios.get(entity) else {
+				continue;
+			};
+			let selected = group.selected.as_deref() == Some(radio.value.as_str());
+			if selected && !checked {
+				commands.entity(entity).insert(Checked);
+			} else if !selected && checked {
+				commands.entity(entity).remove::<Checked>();
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod radio_tests {
+	use bevy::{input::keyboard::Key, input_focus::InputFocus, prelude::*, ui::Checked};
+
+	use super::{HtmlRadioGroup, html_radio_click, html_radio_keyboard, html_radio_sync};
+	use crate::{
+		build::build_tests::{
+			changed_values, click, press_key, spawn_form, spawn_laid_out, x_range,
+		},
+		data::HtmlValue,
+		form::HtmlValueChanged,
+	};
+
+	/// Spawns a form of a single `<radio-group>`, returning it and its radios.
+	fn spawn_group(source: &str) -> (App, Entity, Vec<Entity>) {
+		let (mut app, root) = spawn_form(source);
+		app.add_observer(html_radio_click)
+			.add_systems(Update, (html_radio_keyboard, html_radio_sync).chain());
+		app.update();
+		let group = app.world().get::<Children>(root).unwrap()[0];
+		let radios = app.world().get::<Children>(group).unwrap().to_vec();
+		(app, group, radios)
+	}
+
+	fn checked(app: &App, radios: &[Entity]) -> Vec<bool> {
+		radios
+			.iter()
+			.map(|radio| app.world().entity(*radio).contains::<Checked>())
+			.collect()
+	}
+
+	fn selected(app: &App, group: Entity) -> Option<&str> {
+		app.world()
+			.get::<HtmlRadioGroup>(group)
+			.unwrap()
+			.selected
+			.as_deref()
+	}
+
+	#[test]
+	fn test_radio_label() {
+		let (mut app, root) = spawn_laid_out(
+			"<radio-group name=\"difficulty\"><radio value=\"easy\">Easy</radio></radio-group>",
+		);
+		let world = app.world_mut();
+		let group = world.get::<Children>(root).unwrap()[0];
+		let radio = world.get::<Children>(group).unwrap()[0];
+		assert!(world.get::<Text>(radio).is_none());
+
+		let children: Vec<Entity> = world.get::<Children>(radio).unwrap().to_vec();
+		let [circle, label] = children[..] else {
+			panic!("expected the circle and the label, got {children:?}");
+		};
+		assert_eq!(
+			world.get::<Text>(label).map(|text| text.0.as_str()),
+			Some("Easy")
+		);
+		assert!(world.get::<Node>(label).is_some());
+		assert!(x_range(world, label).0 >= x_range(world, circle).1);
+	}
+
+	/// Clicking a radio selects it, and syncing the group unchecks the radio selected before.
+	#[test]
+	fn test_radio_click() {
+		let (mut app, group, radios) = spawn_group(
+			r#"<radio-group value="easy"><radio value="easy">Easy</radio><radio value="hard">Hard</radio></radio-group>"#,
+		);
+		assert_eq!(checked(&app, &radios), [true, false]);
+
+		click(&mut app, radios[1]);
+		assert_eq!(selected(&app, group), Some("hard"));
+		assert_eq!(app.world().resource::<InputFocus>().get(), Some(radios[1]));
+		assert_eq!(
+			changed_values(&app),
+			vec![HtmlValueChanged {
+				entity: group,
+				value: HtmlValue::String("hard".into()),
+			}]
+		);
+		app.update();
+		assert_eq!(checked(&app, &radios), [false, true]);
+	}
+
+	/// The arrow keys step through the enabled radios of the group, wrapping around at either end.
+	#[test]
+	fn test_radio_step() {
+		let (mut app, group, radios) = spawn_group(
+			r#"<radio-group><radio value="a">A</radio><radio value="b" disabled>B</radio><radio value="c">C</radio></radio-group>"#,
+		);
+		app.world_mut().resource_mut::<InputFocus>().set(radios[0]);
+
+		for (key, expected) in [
+			(Key::ArrowDown, 2),
+			(Key::ArrowDown, 0),
+			(Key::ArrowUp, 2),
+			(Key::ArrowLeft, 0),
+		] {
+			press_key(&mut app, key.clone());
+			app.update();
+			let value = ["a", "b", "c"][expected];
+			assert_eq!(selected(&app, group), Some(value), "{key:?}");
+			assert_eq!(
+				app.world().resource::<InputFocus>().get(),
+				Some(radios[expected])
+			);
+			assert_eq!(
+				changed_values(&app),
+				vec![HtmlValueChanged {
+					entity: group,
+					value: HtmlValue::String(value.into()),
+				}]
+			);
+		}
+		app.update();
+		assert_eq!(checked(&app, &radios), [true, false, false]);
+	}
+
+	/// A `checked` radio sets the selection of its group.
+	#[test]
+	fn test_radio_checked() {
+		let (app, group, radios) = spawn_group(
+			r#"<radio-group><radio value="a">A</radio><radio value="b" checked>B</radio></radio-group>"#,
+		);
+		assert_eq!(selected(&app, group), Some("b"));
+		assert_eq!(checked(&app, &radios), [false, true]);
+	}
+}
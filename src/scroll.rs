@@ -0,0 +1,481 @@
+use bevy::{
+	input::mouse::MouseScrollUnit,
+	input_focus::InputFocus,
+	picking::events::Scroll,
+	prelude::*,
+	ui::{IgnoreScroll, UiGlobalTransform},
+};
+use bevy_flair::prelude::*;
+
+use crate::{ast::HtmlElement, input::HtmlTextInput, tags::HtmlTagHandler};
+
+/// The axes a `<scroll>` scrolls along.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlScroll {
+	pub horizontal: bool,
+	pub vertical: bool,
+}
+
+impl HtmlScroll {
+	/// `1.0` on each axis this scrolls along, and `0.0` on the others.
+	fn axes(self) -> Vec2 {
+		Vec2::new(
+			if self.horizontal { 1.0 } else { 0.0 },
+			if self.vertical { 1.0 } else { 0.0 },
+		)
+	}
+}
+
+/// A scrollbar thumb of a `<scroll>`, drawn inside its `track` over the scrollbar gutter.
+#[derive(Component)]
+pub(crate) struct HtmlScrollThumb {
+	scroll: Entity,
+	track: Entity,
+	vertical: bool,
+}
+
+/// How far one line of a mouse wheel scrolls, in logical pixels.
+const LINE_HEIGHT: f32 = 20.0;
+
+const DEFAULT_SCROLLBAR_WIDTH: f32 = 8.0;
+
+/// `<scroll direction="vertical" scrollbar>`, a box that scrolls its children with the mouse wheel
+/// and touch drags, along `vertical` (the default), `horizontal` or `both` directions.
+///
+/// With the `scrollbar` attribute (optionally set to a width such as `"12px"`), a draggable
+/// scrollbar is drawn with the classes `scroll-track` and `scroll-thumb` for styling.
+#[derive(Default)]
+pub(crate) struct ScrollTag;
+
+impl HtmlTagHandler for ScrollTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let scroll = match element.attribute("direction") {
+			None | Some("vertical") => HtmlScroll {
+				horizontal: false,
+				vertical: true,
+			},
+			Some("horizontal") => HtmlScroll {
+				horizontal: true,
+				vertical: false,
+			},
+			Some("both") => HtmlScroll {
+				horizontal: true,
+				vertical: true,
+			},
+			Some(other) => {
+				warn!(
+					"unknown <scroll> direction `{other}`, expected vertical, horizontal or both"
+				);
+				HtmlScroll {
+					horizontal: false,
+					vertical: true,
+				}
+			}
+		};
+		let scrollbar_width = element.attribute("scrollbar").map(|width| {
+			let width = width.trim();
+			if width.is_empty() {
+				return DEFAULT_SCROLLBAR_WIDTH;
+			}
+			width
+				.strip_suffix("px")
+				.unwrap_or(width)
+				.trim()
+				.parse()
+				.unwrap_or_else(|_| {
+					warn!("invalid <scroll> scrollbar width `{width}`, expected pixels");
+					DEFAULT_SCROLLBAR_WIDTH
+				})
+		});
+
+		entity.insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: if scroll.vertical {
+					FlexDirection::Column
+				} else {
+					FlexDirection::Row
+				},
+				row_gap: element.gap,
+				column_gap: element.gap,
+				overflow: Overflow {
+					x: if scroll.horizontal {
+						OverflowAxis::Scroll
+					} else {
+						OverflowAxis::Clip
+					},
+					y: if scroll.vertical {
+						OverflowAxis::Scroll
+					} else {
+						OverflowAxis::Clip
+					},
+				},
+				scrollbar_width: scrollbar_width.unwrap_or(0.0),
+				..default()
+			},
+			ScrollPosition::default(),
+			scroll,
+		));
+
+		if scrollbar_width.is_some() {
+			spawn_scrollbars(entity, scroll);
+		}
+	}
+}
+
+/// Spawns a track with a draggable thumb for each axis `scroll` scrolls along, kept in place over
+/// the scrollbar gutters by [`html_scroll_update_scrollbars`].
+fn spawn_scrollbars(entity: &mut EntityWorldMut, scroll: HtmlScroll) {
+	let id = entity.id();
+	let axes = [(scroll.vertical, true), (scroll.horizontal, false)];
+	let tracks: Vec<Entity> = entity.world_scope(|world| {
+		axes.into_iter()
+			.filter(|(enabled, _)| *enabled)
+			.map(|(_, vertical)| {
+				let track = world
+					.spawn((
+						Node {
+							position_type: PositionType::Absolute,
+							..default()
+						},
+						IgnoreScroll(BVec2::TRUE),
+						ZIndex(1),
+						BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.1)),
+						ClassList::new("scroll-track"),
+						Pickable::IGNORE,
+					))
+					.id();
+				let thumb = world
+					.spawn((
+						Node {
+							position_type: PositionType::Absolute,
+							..default()
+						},
+						BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
+						ClassList::new("scroll-thumb"),
+						HtmlScrollThumb {
+							scroll: id,
+							track,
+							vertical,
+						},
+						Pickable {
+							is_hoverable: true,
+							should_block_lower: true,
+						},
+					))
+					.id();
+				world.entity_mut(track).add_child(thumb);
+				track
+			})
+			.collect()
+	});
+	entity.add_children(&tracks);
+}
+
+/// Scrolls `position` by `delta` logical pixels, staying within the content of `node`, and returns
+/// whether it moved.
+fn scroll_by(position: &mut ScrollPosition, node: &ComputedNode, delta: Vec2) -> bool {
+	let max = (node.content_size - node.size + node.scrollbar_size).max(Vec2::ZERO)
+		* node.inverse_scale_factor();
+	let scrolled = (position.0 + delta).clamp(Vec2::ZERO, max);
+	if position.0 == scrolled {
+		return false;
+	}
+	position.0 = scrolled;
+	true
+}
+
+/// The scroll offset closest to `offset` that shows `min..max` within a `visible` length.
+pub(crate) fn scroll_into_view(offset: f32, min: f32, max: f32, visible: f32) -> f32 {
+	if min < offset {
+		min
+	} else if max > offset + visible {
+		(max - visible).min(min)
+	} else {
+		offset
+	}
+}
+
+/// Scrolls a `<scroll>` or `<textarea>` under the mouse wheel, or a horizontal `<scroll>` with a
+/// vertical wheel. Once it cannot scroll any further, the wheel scrolls the one around it.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_scroll_wheel(
+	mut wheel: On<Pointer<Scroll>>,
+	mut scrolls: Query<(
+		Option<&HtmlScroll>,
+		Option<&HtmlTextInput>,
+		&mut ScrollPosition,
+		&ComputedNode,
+	)>,
+) {
+	let Ok((scroll, input, mut position, node)) = scrolls.get_mut(wheel.entity) else {
+		return;
+	};
+	let scroll = match (scroll, input) {
+		(Some(scroll), _) => *scroll,
+		(None, Some(input)) if input.multiline => HtmlScroll {
+			horizontal: false,
+			vertical: true,
+		},
+		_ => return,
+	};
+	let mut delta = Vec2::new(wheel.x, wheel.y);
+	if wheel.unit == MouseScrollUnit::Line {
+		delta *= LINE_HEIGHT;
+	}
+	if !scroll.vertical && delta.x == 0.0 {
+		delta = Vec2::new(delta.y, 0.0);
+	}
+	if scroll_by(&mut position, node, -delta * scroll.axes()) {
+		wheel.propagate(false);
+	}
+}
+
+/// Scrolls a `<scroll>` by dragging a finger over it, or by dragging one of its scrollbar thumbs.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_scroll_drag(
+	mut drag: On<Pointer<Drag>>,
+	thumbs: Query<&HtmlScrollThumb>,
+	mut scrolls: Query<(&HtmlScroll, &mut ScrollPosition, &ComputedNode)>,
+) {
+	if let Ok(thumb) = thumbs.get(drag.entity) {
+		drag.propagate(false);
+		let Ok((_, mut position, node)) = scrolls.get_mut(thumb.scroll) else {
+			return;
+		};
+		let scrollbar = if thumb.vertical {
+			node.vertical_scrollbar()
+		} else {
+			node.horizontal_scrollbar()
+		};
+		let Some((gutter, _)) = scrollbar else {
+			return;
+		};
+		// The thumb covers the same share of the gutter as the view does of the content.
+		let (delta, ratio) = if thumb.vertical {
+			(
+				Vec2::new(0.0, drag.delta.y),
+				node.content_size.y / gutter.height(),
+			)
+		} else {
+			(
+				Vec2::new(drag.delta.x, 0.0),
+				node.content_size.x / gutter.width(),
+			)
+		};
+		if ratio.is_finite() {
+			scroll_by(&mut position, node, delta * ratio);
+		}
+		return;
+	}
+
+	if !drag.pointer_id.is_touch() {
+		return;
+	}
+	let Ok((scroll, mut position, node)) = scrolls.get_mut(drag.entity) else {
+		return;
+	};
+	if scroll_by(&mut position, node, -drag.delta * scroll.axes()) {
+		drag.propagate(false);
+	}
+}
+
+/// Scrolls every `<scroll>` around the newly focused element so that it is in view, such as when
+/// moving through a long list with directional navigation.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_scroll_focus_into_view(
+	focus: Res<InputFocus>,
+	parents: Query<&ChildOf>,
+	nodes: Query<(&ComputedNode, &UiGlobalTransform)>,
+	mut scrolls: Query<(&HtmlScroll, &mut ScrollPosition)>,
+) {
+	let Some(focused) = focus.get() else {
+		return;
+	};
+	let Ok((item, item_transform)) = nodes.get(focused) else {
+		return;
+	};
+	let item_center = item_transform.translation;
+	let item_half = item.size / 2.0;
+
+	for ancestor in parents.iter_ancestors(focused) {
+		let Ok((scroll, mut position)) = scrolls.get_mut(ancestor) else {
+			continue;
+		};
+		let Ok((node, transform)) = nodes.get(ancestor) else {
+			continue;
+		};
+		// All in physical pixels, from the top left corner of the visible content.
+		let inset = node.content_inset();
+		let view_min = transform.translation - node.size / 2.0 + inset.min_inset;
+		let visible = node.size - inset.min_inset - inset.max_inset;
+		let min = item_center - item_half - view_min + node.scroll_position;
+		let max = item_center + item_half - view_min + node.scroll_position;
+
+		let mut target = node.scroll_position;
+		if scroll.horizontal {
+			target.x = scroll_into_view(target.x, min.x, max.x, visible.x);
+		}
+		if scroll.vertical {
+			target.y = scroll_into_view(target.y, min.y, max.y, visible.y);
+		}
+		if target != node.scroll_position {
+			position.0 = target * node.inverse_scale_factor();
+		}
+	}
+}
+
+/// Places the scrollbar tracks and thumbs of each `<scroll>` over its scrollbar gutters.
+pub(crate) fn html_scroll_update_scrollbars(
+	thumbs: Query<(Entity, &HtmlScrollThumb)>,
+	scrolls: Query<&ComputedNode, With<HtmlScroll>>,
+	mut nodes: Query<&mut Node>,
+) {
+	for (entity, thumb) in &thumbs {
+		let Ok(node) = scrolls.get(thumb.scroll) else {
+			continue;
+		};
+		let scrollbar = if thumb.vertical {
+			node.vertical_scrollbar()
+		} else {
+			node.horizontal_scrollbar()
+		};
+		let scale = node.inverse_scale_factor();
+		// The gutter is relative to the center of the scroll, and absolute positions to the
+		// inside of its border.
+		let (gutter, [thumb_min, thumb_max]) = scrollbar.unwrap_or_default();
+		let offset = node.size / 2.0 - node.border.min_inset;
+		if let Ok(mut track) = nodes.get_mut(thumb.track) {
+			let left = Val::Px((gutter.min.x + offset.x) * scale);
+			let top = Val::Px((gutter.min.y + offset.y) * scale);
+			let width = Val::Px(gutter.width() * scale);
+			let height = Val::Px(gutter.height() * scale);
+			if track.left != left
+				|| track.top != top
+				|| track.width != width
+				|| track.height != height
+			{
+				track.left = left;
+				track.top = top;
+				track.width = width;
+				track.height = height;
+			}
+		}
+		if let Ok(mut thumb_node) = nodes.get_mut(entity) {
+			let (start, length) = if thumb.vertical {
+				(thumb_min - gutter.min.y, thumb_max - thumb_min)
+			} else {
+				(thumb_min - gutter.min.x, thumb_max - thumb_min)
+			};
+			let (left, top, width, height) = if thumb.vertical {
+				(
+					Val::Px(0.0),
+					Val::Px(start * scale),
+					Val::Percent(100.0),
+					Val::Px(length * scale),
+				)
+			} else {
+				(
+					Val::Px(start * scale),
+					Val::Px(0.0),
+					Val::Px(length * scale),
+					Val::Percent(100.0),
+				)
+			};
+			if thumb_node.left != left
+				|| thumb_node.top != top
+				|| thumb_node.width != width
+				|| thumb_node.height != height
+			{
+				thumb_node.left = left;
+				thumb_node.top = top;
+				thumb_node.width = width;
+				thumb_node.height = height;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod scroll_tests {
+	use bevy::{input::mouse::MouseScrollUnit, picking::events::Scroll, prelude::*};
+
+	use super::{html_scroll_wheel, scroll_by, scroll_into_view};
+	use crate::build::build_tests::{hit, pointer, spawn_laid_out};
+
+	/// An offset already showing the range stays, and otherwise moves just enough to show it.
+	#[test]
+	fn test_scroll_into_view() {
+		assert_eq!(scroll_into_view(10.0, 20.0, 30.0, 50.0), 10.0);
+		assert_eq!(scroll_into_view(10.0, 0.0, 10.0, 50.0), 0.0);
+		assert_eq!(scroll_into_view(10.0, 50.0, 70.0, 50.0), 20.0);
+		// A range longer than the view shows its start.
+		assert_eq!(scroll_into_view(0.0, 40.0, 120.0, 50.0), 40.0);
+	}
+
+	/// Scrolling stops at either end of the content, counting the scrollbar, in logical pixels.
+	#[test]
+	fn test_scroll_by() {
+		let node = ComputedNode {
+			size: Vec2::new(200.0, 100.0),
+			content_size: Vec2::new(200.0, 300.0),
+			scrollbar_size: Vec2::new(0.0, 20.0),
+			inverse_scale_factor: 0.5,
+			..default()
+		};
+		let mut position = ScrollPosition::default();
+		assert!(scroll_by(&mut position, &node, Vec2::new(0.0, 60.0)));
+		assert_eq!(position.0, Vec2::new(0.0, 60.0));
+		assert!(scroll_by(&mut position, &node, Vec2::new(50.0, 100.0)));
+		assert_eq!(position.0, Vec2::new(0.0, 110.0));
+		assert!(!scroll_by(&mut position, &node, Vec2::new(0.0, 10.0)));
+		assert!(scroll_by(&mut position, &node, Vec2::new(0.0, -200.0)));
+		assert_eq!(position.0, Vec2::ZERO);
+		assert!(!scroll_by(&mut position, &node, Vec2::new(0.0, -10.0)));
+	}
+
+	/// The wheel over a child scrolls the innermost `<scroll>` around it until that reaches its end,
+	/// and then the one around that.
+	#[test]
+	fn test_scroll_wheel() {
+		let (mut app, root) =
+			spawn_laid_out("<scroll><scroll><node></node></scroll><node></node></scroll>");
+		app.add_observer(html_scroll_wheel);
+		let outer = app.world().get::<Children>(root).unwrap()[0];
+		let [inner, filler] = app.world().get::<Children>(outer).unwrap()[..] else {
+			panic!("expected two children");
+		};
+		let item = app.world().get::<Children>(inner).unwrap()[0];
+		// The outer scroll shows 50 of 140 pixels, and the inner one 40 of 60.
+		let world = app.world_mut();
+		world.get_mut::<Node>(outer).unwrap().width = Val::Px(100.0);
+		world.get_mut::<Node>(outer).unwrap().height = Val::Px(50.0);
+		world.get_mut::<Node>(inner).unwrap().height = Val::Px(40.0);
+		world.get_mut::<Node>(inner).unwrap().flex_shrink = 0.0;
+		world.get_mut::<Node>(item).unwrap().min_height = Val::Px(60.0);
+		world.get_mut::<Node>(filler).unwrap().min_height = Val::Px(100.0);
+		app.update();
+
+		let wheel = |app: &mut App| {
+			app.world_mut().trigger(pointer(
+				item,
+				Scroll {
+					unit: MouseScrollUnit::Line,
+					x: 0.0,
+					y: -1.0,
+					hit: hit(),
+				},
+			));
+			app.update();
+			let position = |entity| app.world().get::<ScrollPosition>(entity).unwrap().y;
+			(position(inner), position(outer))
+		};
+		assert_eq!(wheel(&mut app), (20.0, 0.0));
+		assert_eq!(wheel(&mut app), (20.0, 20.0));
+		assert_eq!(wheel(&mut app), (20.0, 40.0));
+		assert_eq!(wheel(&mut app), (20.0, 60.0));
+		assert_eq!(wheel(&mut app), (20.0, 80.0));
+		assert_eq!(wheel(&mut app), (20.0, 90.0));
+		assert_eq!(wheel(&mut app), (20.0, 90.0));
+	}
+}
@@ -0,0 +1,676 @@
+use bevy::{
+	input::{
+		ButtonState,
+		keyboard::{Key, KeyboardInput},
+	},
+	input_focus::InputFocus,
+	picking::hover::Hovered,
+	prelude::*,
+	ui::{Checked, InteractionDisabled, auto_directional_navigation::AutoDirectionalNavigation},
+};
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::{HtmlElement, HtmlNode},
+	build::spawn_node,
+	data::HtmlValue,
+	form::{ConfirmInput, HtmlValueChanged, StepInput},
+	tags::HtmlTagHandler,
+};
+
+/// The value of a `<select>` and whether its list of options is open.
+///
+/// Setting either from code updates the select without sending [`HtmlValueChanged`].
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlSelect {
+	/// The `value` of the chosen `<option>`. Defaults to the first option.
+	pub value: Option<String>,
+	pub open: bool,
+}
+
+/// An `<option value="...">` of a [`HtmlSelect`], which has bevy's [`Checked`] component while it
+/// is the chosen one.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct HtmlOption {
+	pub value: String,
+}
+
+/// The parts of a `<select>`: the text showing the chosen option, and the popup holding the options.
+#[derive(Component)]
+pub(crate) struct HtmlSelectParts {
+	label: Entity,
+	popup: Entity,
+}
+
+/// Draws open popups above the rest of the UI.
+const POPUP_Z_INDEX: i32 = 1000;
+
+/// How long after the last typed character the next one starts a new search, in seconds.
+const TYPE_AHEAD_TIMEOUT: f64 = 1.0;
+
+/// `<select value="1080p"><option value="1080p">1920×1080</option></select>`, a field showing the
+/// chosen option that opens a list of its options when clicked.
+///
+/// Drawn with the classes `select-label` and `select-popup` for styling, with `select-option` added
+/// to every option.
+#[derive(Default)]
+pub(crate) struct SelectTag;
+
+impl HtmlTagHandler for SelectTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let parts = entity.world_scope(|world| {
+			let label = world
+				.spawn((
+					Text::default(),
+					ClassList::new("select-label"),
+					Pickable::IGNORE,
+				))
+				.id();
+			let popup = world
+				.spawn((
+					Node {
+						display: Display::None,
+						position_type: PositionType::Absolute,
+						top: Val::Percent(100.0),
+						left: Val::Px(0.0),
+						min_width: Val::Percent(100.0),
+						flex_direction: FlexDirection::Column,
+						..default()
+					},
+					GlobalZIndex(POPUP_Z_INDEX),
+					BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+					ClassList::new("select-popup"),
+					Pickable {
+						is_hoverable: true,
+						should_block_lower: true,
+					},
+				))
+				.id();
+			HtmlSelectParts { label, popup }
+		});
+
+		let popup = parts.popup;
+		entity.add_children(&[parts.label, popup]).insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: FlexDirection::Row,
+				align_items: AlignItems::Center,
+				min_width: Val::Px(120.0),
+				padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+				border: UiRect::all(Val::Px(2.0)),
+				..default()
+			},
+			BorderColor::all(Color::WHITE),
+			HtmlSelect {
+				value: element.attribute("value").map(str::to_owned),
+				open: false,
+			},
+			parts,
+			Hovered::default(),
+			AutoDirectionalNavigation::default(),
+			Pickable {
+				is_hoverable: true,
+				should_block_lower: true,
+			},
+		));
+		if element.attribute("disabled").is_some() {
+			entity.insert(InteractionDisabled);
+		}
+
+		// The options go in the popup rather than next to the label.
+		entity.world_scope(|world| {
+			for child in &element.children {
+				spawn_node(world, popup, child);
+			}
+		});
+	}
+
+	fn spawn_children(&self) -> bool {
+		false
+	}
+}
+
+/// `<option value="1080p" selected>1920×1080</option>`, one choice of a `<select>`. Its value
+/// defaults to its text, like in HTML.
+#[derive(Default)]
+pub(crate) struct OptionTag;
+
+impl HtmlTagHandler for OptionTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let value = element.attribute("value").map_or_else(
+			|| {
+				element
+					.children
+					.iter()
+					.filter_map(|child| match child {
+						HtmlNode::Text(text) => Some(text.trim()),
+						HtmlNode::Element(_) => None,
+					})
+					.collect()
+			},
+			str::to_owned,
+		);
+
+		if element.attribute("selected").is_some() {
+			let id = entity.id();
+			let value = value.clone();
+			entity.world_scope(|world| {
+				let mut ancestor = world.get::<ChildOf>(id).map(ChildOf::parent);
+				while let Some(current) = ancestor {
+					if let Some(mut select) = world.get_mut::<HtmlSelect>(current) {
+						select.value = Some(value);
+						break;
+					}
+					ancestor = world.get::<ChildOf>(current).map(ChildOf::parent);
+				}
+			});
+		}
+
+		if let Some(mut classes) = entity.get_mut::<ClassList>() {
+			classes.add("select-option");
+		} else {
+			entity.insert(ClassList::new("select-option"));
+		}
+		entity.insert((
+			Node {
+				padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+				..default()
+			},
+			HtmlOption { value },
+			Pickable {
+				is_hoverable: true,
+				should_block_lower: true,
+			},
+		));
+		if element.attribute("disabled").is_some() {
+			entity.insert(InteractionDisabled);
+		}
+	}
+}
+
+/// The enabled options of the select with `parts`, in document order.
+fn enabled_options(
+	parts: &HtmlSelectParts,
+	children: &Query<&Children>,
+	options: &Query<(&HtmlOption, Option<&Text>, Has<InteractionDisabled>)>,
+) -> Vec<Entity> {
+	children
+		.iter_descendants_depth_first(parts.popup)
+		.filter(|entity| options.get(*entity).is_ok_and(|(_, _, disabled)| !disabled))
+		.collect()
+}
+
+/// Makes `value` the value of `select`, reporting it if it changed.
+fn choose(
+	entity: Entity,
+	select: &mut Mut<HtmlSelect>,
+	value: &str,
+	changed: &mut MessageWriter<HtmlValueChanged>,
+) {
+	if select.value.as_deref() == Some(value) {
+		return;
+	}
+	select.value = Some(value.to_owned());
+	changed.write(HtmlValueChanged {
+		entity,
+		value: HtmlValue::String(value.to_owned()),
+	});
+}
+
+/// Opens or closes a clicked `<select>`, or chooses a clicked `<option>` and closes its select.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_select_click(
+	mut click: On<Pointer<Click>>,
+	mut selects: Query<(&mut HtmlSelect, Has<InteractionDisabled>)>,
+	options: Query<(&HtmlOption, Has<InteractionDisabled>)>,
+	parents: Query<&ChildOf>,
+	mut focus: ResMut<InputFocus>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	let target = click.entity;
+	if let Ok((option, disabled)) = options.get(target) {
+		// Stop the click from reaching the select, which would open it again.
+		click.propagate(false);
+		if disabled {
+			return;
+		}
+		let Some(select_entity) = parents
+			.iter_ancestors(target)
+			.find(|ancestor| selects.contains(*ancestor))
+		else {
+			return;
+		};
+		if let Ok((mut select, _)) = selects.get_mut(select_entity) {
+			choose(select_entity, &mut select, &option.value, &mut changed);
+			select.open = false;
+			focus.set(select_entity);
+		}
+		return;
+	}
+
+	if let Ok((mut select, disabled)) = selects.get_mut(target) {
+		focus.set(target);
+		if !disabled {
+			select.open = !select.open;
+		}
+	}
+}
+
+/// What was typed recently to jump to an option by its text.
+#[derive(Default)]
+pub(crate) struct TypeAhead {
+	text: String,
+	last_typed: f64,
+}
+
+/// Drives the focused `<select>` from the keyboard or a gamepad.
+///
+/// While closed, confirming opens it, and stepping up or down or typing the start of an option's
+/// text chooses an option directly. While open, stepping and typing move the focus through the
+/// options, confirming chooses the focused option, and Escape or the East button (B on Xbox
+/// controllers) closes it.
+#[allow(clippy::too_many_arguments, clippy::needless_pass_by_value)]
+pub(crate) fn html_select_keyboard(
+	mut confirm: ConfirmInput,
+	mut step: StepInput,
+	mut keyboard: MessageReader<KeyboardInput>,
+	gamepads: Query<&Gamepad>,
+	time: Res<Time>,
+	mut type_ahead: Local<TypeAhead>,
+	mut focus: ResMut<InputFocus>,
+	mut selects: Query<(
+		Entity,
+		&mut HtmlSelect,
+		&HtmlSelectParts,
+		Has<InteractionDisabled>,
+	)>,
+	options: Query<(&HtmlOption, Option<&Text>, Has<InteractionDisabled>)>,
+	children: Query<&Children>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	let confirmed = confirm.just_confirmed();
+	let step = step.just_stepped().y;
+	let mut cancelled = gamepads
+		.iter()
+		.any(|gamepad| gamepad.just_pressed(GamepadButton::East));
+	let mut typed = String::new();
+	for event in keyboard.read() {
+		if event.state != ButtonState::Pressed {
+			continue;
+		}
+		match &event.logical_key {
+			Key::Escape => cancelled = true,
+			Key::Character(_) => typed.extend(event.text.as_deref()),
+			_ => {}
+		}
+	}
+	if !confirmed && !cancelled && step == 0 && typed.is_empty() {
+		return;
+	}
+
+	let Some(focused) = focus.get() else {
+		return;
+	};
+	let Some((entity, mut select, parts, false)) =
+		selects.iter_mut().find(|(entity, select, parts, _)| {
+			*entity == focused
+				|| (select.open
+					&& children
+						.iter_descendants(parts.popup)
+						.any(|descendant| descendant == focused))
+		})
+	else {
+		return;
+	};
+
+	let list = enabled_options(parts, &children, &options);
+	let current = list
+		.iter()
+		.position(|option| *option == focused)
+		.or_else(|| {
+			list.iter().position(|option| {
+				options
+					.get(*option)
+					.is_ok_and(|(option, _, _)| select.value.as_ref() == Some(&option.value))
+			})
+		});
+
+	let mut target = None;
+	if !typed.is_empty() {
+		let now = time.elapsed_secs_f64();
+		if now - type_ahead.last_typed > TYPE_AHEAD_TIMEOUT {
+			type_ahead.text.clear();
+		}
+		type_ahead.last_typed = now;
+		type_ahead.text.push_str(&typed.to_lowercase());
+		target = list.iter().position(|option| {
+			options.get(*option).is_ok_and(|(_, text, _)| {
+				text.is_some_and(|text| text.0.trim().to_lowercase().starts_with(&type_ahead.text))
+			})
+		});
+	}
+	if target.is_none() && step != 0 && !list.is_empty() {
+		target = Some(current.map_or(0, |index| {
+			index
+				.saturating_add_signed(step as isize)
+				.min(list.len() - 1)
+		}));
+	}
+
+	if select.open {
+		if confirmed {
+			if let Some(option) = current.and_then(|index| options.get(list[index]).ok()) {
+				choose(entity, &mut select, &option.0.value, &mut changed);
+			}
+			select.open = false;
+			focus.set(entity);
+		} else if cancelled {
+			select.open = false;
+			focus.set(entity);
+		} else if let Some(index) = target {
+			focus.set(list[index]);
+		}
+	} else if confirmed {
+		select.open = true;
+		if let Some(index) = current.or(if list.is_empty() { None } else { Some(0) }) {
+			focus.set(list[index]);
+		}
+	} else if let Some((option, _, _)) = target.and_then(|index| options.get(list[index]).ok()) {
+		choose(entity, &mut select, &option.value, &mut changed);
+	}
+}
+
+/// Closes each open `<select>` when a mouse button or touch is pressed outside of it.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_select_close_outside(
+	mouse: Option<Res<ButtonInput<MouseButton>>>,
+	touches: Option<Res<Touches>>,
+	mut focus: ResMut<InputFocus>,
+	mut selects: Query<(Entity, &mut HtmlSelect, &HtmlSelectParts, &Hovered)>,
+	children: Query<&Children>,
+) {
+	let pressed = mouse.is_some_and(|mouse| mouse.get_just_pressed().next().is_some())
+		|| touches.is_some_and(|touches| touches.any_just_pressed());
+	if !pressed {
+		return;
+	}
+	for (entity, mut select, parts, hovered) in &mut selects {
+		if !select.open || hovered.get() {
+			continue;
+		}
+		select.open = false;
+		if let Some(focused) = focus.get()
+			&& children
+				.iter_descendants(parts.popup)
+				.any(|descendant| descendant == focused)
+		{
+			focus.set(entity);
+		}
+	}
+}
+
+/// Shows or hides the popup of each `<select>`, shows the text of its chosen option, and marks that
+/// option [`Checked`], for the selects that changed or have new or changed options.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn html_select_update_display(
+	mut commands: Commands,
+	changed_options: Query<Entity, (With<HtmlOption>, Or<(Added<HtmlOption>, Changed<Text>)>)>,
+	parents: Query<&ChildOf>,
+	mut selects: Query<(Entity, &mut HtmlSelect, &HtmlSelectParts)>,
+	options: Query<(
+		&HtmlOption,
+		Option<&Text>,
+		Has<Checked>,
+		Has<InteractionDisabled>,
+	)>,
+	children: Query<&Children>,
+	mut nodes: Query<&mut Node>,
+	mut labels: Query<&mut Text, Without<HtmlOption>>,
+) {
+	let mut dirty: Vec<Entity> = selects
+		.iter_mut()
+		.filter(|(_, select, _)| select.is_changed())
+		.map(|(entity, ..)| entity)
+		.collect();
+	for option in &changed_options {
+		if let Some(select) = parents
+			.iter_ancestors(option)
+			.find(|ancestor| selects.contains(*ancestor))
+			&& !dirty.contains(&select)
+		{
+			dirty.push(select);
+		}
+	}
+
+	for entity in dirty {
+		let Ok((_, mut select, parts)) = selects.get_mut(entity) else {
+			continue;
+		};
+		if select.value.is_none() {
+			// Like in HTML, a select without a chosen option picks its first one.
+			let first = children
+				.iter_descendants_depth_first(parts.popup)
+				.filter_map(|entity| options.get(entity).ok())
+				.find(|(_, _, _, disabled)| !disabled);
+			if let Some((option, ..)) = first {
+				select.value = Some(option.value.clone());
+			}
+		}
+
+		let mut label = "";
+		for entity in children.iter_descendants(parts.popup) {
+			let Ok((option, text, checked)) = options
+				.get(entity)
+				.map(|(option, text, checked, _)| (option, text, checked))
+			else {
+				continue;
+			};
+			let chosen = select.value.as_ref() == Some(&option.value);
+			if chosen {
+				label = text.map_or("", |text| text.0.trim());
+			}
+			if chosen && !checked {
+				commands.entity(entity).insert(Checked);
+			} else if !chosen && checked {
+				commands.entity(entity).remove::<Checked>();
+			}
+		}
+		if let Ok(mut text) = labels.get_mut(parts.label)
+			&& text.0 != label
+		{
+			label.clone_into(&mut text.0);
+		}
+
+		if let Ok(mut popup) = nodes.get_mut(parts.popup) {
+			let display = if select.open {
+				Display::Flex
+			} else {
+				Display::None
+			};
+			if popup.display != display {
+				popup.display = display;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod select_tests {
+	use std::time::Duration;
+
+	use bevy::{input::keyboard::Key, input_focus::InputFocus, prelude::*, ui::Checked};
+
+	use super::{
+		HtmlSelect, HtmlSelectParts, html_select_click, html_select_close_outside,
+		html_select_keyboard, html_select_update_display,
+	};
+	use crate::{
+		build::build_tests::{changed_values, click, press_key, spawn_form},
+		data::HtmlValue,
+		form::HtmlValueChanged,
+	};
+
+	/// Spawns a form of a single `<select>`, returning it and its options.
+	fn spawn_select(source: &str) -> (App, Entity, Vec<Entity>) {
+		let (mut app, root) = spawn_form(source);
+		app.init_resource::<Time>()
+			.add_observer(html_select_click)
+			.add_systems(
+				Update,
+				(
+					html_select_keyboard,
+					html_select_close_outside,
+					html_select_update_display,
+				)
+					.chain(),
+			);
+		app.update();
+		let select = app.world().get::<Children>(root).unwrap()[0];
+		let popup = app.world().get::<HtmlSelectParts>(select).unwrap().popup;
+		let options = app.world().get::<Children>(popup).unwrap().to_vec();
+		(app, select, options)
+	}
+
+	fn value(app: &App, select: Entity) -> Option<&str> {
+		app.world()
+			.get::<HtmlSelect>(select)
+			.unwrap()
+			.value
+			.as_deref()
+	}
+
+	fn is_open(app: &App, select: Entity) -> bool {
+		app.world().get::<HtmlSelect>(select).unwrap().open
+	}
+
+	fn label(app: &App, select: Entity) -> &str {
+		let label = app.world().get::<HtmlSelectParts>(select).unwrap().label;
+		&app.world().get::<Text>(label).unwrap().0
+	}
+
+	fn focused(app: &App) -> Option<Entity> {
+		app.world().resource::<InputFocus>().get()
+	}
+
+	fn changed_to(select: Entity, value: &str) -> Vec<HtmlValueChanged> {
+		vec![HtmlValueChanged {
+			entity: select,
+			value: HtmlValue::String(value.into()),
+		}]
+	}
+
+	/// Clicking opens the popup, and clicking an option chooses it, closes the popup and marks the
+	/// option checked.
+	#[test]
+	fn test_select_popup() {
+		let (mut app, select, options) = spawn_select(
+			r#"<select><option value="a">Alpha</option><option value="b">Beta</option></select>"#,
+		);
+		let popup = app.world().get::<HtmlSelectParts>(select).unwrap().popup;
+		assert_eq!(value(&app, select), Some("a"));
+		assert_eq!(label(&app, select), "Alpha");
+		assert!(app.world().entity(options[0]).contains::<Checked>());
+		assert_eq!(
+			app.world().get::<Node>(popup).unwrap().display,
+			Display::None
+		);
+
+		click(&mut app, select);
+		app.update();
+		assert!(is_open(&app, select));
+		assert_eq!(
+			app.world().get::<Node>(popup).unwrap().display,
+			Display::Flex
+		);
+
+		click(&mut app, options[1]);
+		assert_eq!(value(&app, select), Some("b"));
+		assert!(!is_open(&app, select));
+		assert_eq!(focused(&app), Some(select));
+		assert_eq!(changed_values(&app), changed_to(select, "b"));
+		app.update();
+		assert_eq!(label(&app, select), "Beta");
+		assert!(!app.world().entity(options[0]).contains::<Checked>());
+		assert!(app.world().entity(options[1]).contains::<Checked>());
+		assert_eq!(
+			app.world().get::<Node>(popup).unwrap().display,
+			Display::None
+		);
+	}
+
+	/// Typing the start of an option's text chooses it, with characters typed within a second of
+	/// each other searching together.
+	#[test]
+	fn test_select_type_ahead() {
+		let (mut app, select, _) = spawn_select(
+			"<select><option>Alpha</option><option>Beta</option><option>Bravo</option><option>Charlie</option></select>",
+		);
+		app.world_mut().resource_mut::<InputFocus>().set(select);
+		let type_after = |app: &mut App, seconds: f64, text: &str| {
+			app.world_mut()
+				.resource_mut::<Time>()
+				.advance_by(Duration::from_secs_f64(seconds));
+			press_key(app, Key::Character(text.into()));
+			app.update();
+		};
+
+		type_after(&mut app, 0.0, "b");
+		assert_eq!(value(&app, select), Some("Beta"));
+		assert_eq!(changed_values(&app), changed_to(select, "Beta"));
+		type_after(&mut app, 0.5, "r");
+		assert_eq!(value(&app, select), Some("Bravo"));
+		type_after(&mut app, 1.5, "c");
+		assert_eq!(value(&app, select), Some("Charlie"));
+
+		type_after(&mut app, 1.5, "b");
+		assert_eq!(value(&app, select), Some("Beta"));
+		// A second later, `r` starts a new search that matches nothing.
+		type_after(&mut app, 1.5, "r");
+		assert_eq!(value(&app, select), Some("Beta"));
+		assert!(changed_values(&app).is_empty());
+	}
+
+	/// Confirming opens the popup and chooses the focused option, Escape closes it without choosing,
+	/// and so does pressing outside of it.
+	#[test]
+	fn test_select_close() {
+		let (mut app, select, options) = spawn_select(
+			r#"<select><option value="a">Alpha</option><option value="b">Beta</option></select>"#,
+		);
+		app.world_mut().resource_mut::<InputFocus>().set(select);
+
+		press_key(&mut app, Key::Enter);
+		app.update();
+		assert!(is_open(&app, select));
+		assert_eq!(focused(&app), Some(options[0]));
+		press_key(&mut app, Key::Escape);
+		app.update();
+		assert!(!is_open(&app, select));
+		assert_eq!(focused(&app), Some(select));
+		assert_eq!(value(&app, select), Some("a"));
+
+		press_key(&mut app, Key::Enter);
+		app.update();
+		press_key(&mut app, Key::ArrowDown);
+		app.update();
+		assert_eq!(focused(&app), Some(options[1]));
+		press_key(&mut app, Key::Enter);
+		app.update();
+		assert!(!is_open(&app, select));
+		assert_eq!(value(&app, select), Some("b"));
+		assert_eq!(changed_values(&app), changed_to(select, "b"));
+		app.update();
+		assert!(app.world().entity(options[1]).contains::<Checked>());
+
+		press_key(&mut app, Key::Enter);
+		app.update();
+		assert!(is_open(&app, select));
+		let mut mouse = ButtonInput::<MouseButton>::default();
+		mouse.press(MouseButton::Left);
+		app.insert_resource(mouse);
+		app.update();
+		assert!(!is_open(&app, select));
+		assert_eq!(focused(&app), Some(select));
+		assert_eq!(value(&app, select), Some("b"));
+	}
+}
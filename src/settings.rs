@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Deserialize, Serialize)] // Settings + Default + Serialize + for<'a> Deserialize<'a>
-pub struct HtmlUiSettings;
+pub struct HtmlUiSettings {
+	/// How many `<include>`s deep this document is loaded, which stops an include cycle.
+	#[serde(default)]
+	pub(crate) include_depth: usize,
+}
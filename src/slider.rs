@@ -0,0 +1,563 @@
+use bevy::{
+	input_focus::InputFocus,
+	prelude::*,
+	ui::{InteractionDisabled, auto_directional_navigation::AutoDirectionalNavigation},
+};
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::HtmlElement,
+	data::HtmlValue,
+	form::{HtmlValueChanged, StepInput},
+	tags::HtmlTagHandler,
+};
+
+/// The range and value of a `<slider>`.
+///
+/// Setting the value from code (preferably with [`HtmlSlider::set_value`]) moves the thumb without
+/// sending [`HtmlValueChanged`].
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct HtmlSlider {
+	pub min: f32,
+	pub max: f32,
+	/// The increment the value snaps to, or `0.0` for any value (`step="any"`).
+	pub step: f32,
+	pub value: f32,
+	/// Whether the slider runs bottom to top instead of left to right.
+	pub vertical: bool,
+}
+
+impl HtmlSlider {
+	/// Sets the value, clamped to `min..=max` and snapped to `step`, to the step below `max` when
+	/// the range does not end on one.
+	pub fn set_value(&mut self, value: f32) {
+		let max = self.max.max(self.min);
+		let mut value = value.clamp(self.min, max);
+		if self.step > 0.0 {
+			value = self.min + ((value - self.min) / self.step).round() * self.step;
+			if value > max {
+				value = (value - self.step).max(self.min);
+			}
+		}
+		self.value = value;
+	}
+
+	/// How far along the range the value is, from `0.0` to `1.0`.
+	#[must_use]
+	pub fn fraction(&self) -> f32 {
+		let range = self.max - self.min;
+		if range > 0.0 {
+			((self.value - self.min) / range).clamp(0.0, 1.0)
+		} else {
+			0.0
+		}
+	}
+
+	/// The amount a single arrow key or D-pad press changes the value by.
+	fn key_step(&self) -> f32 {
+		if self.step > 0.0 {
+			self.step
+		} else {
+			(self.max - self.min) / 100.0
+		}
+	}
+}
+
+/// The parts of a `<slider>` moved to match its value.
+#[derive(Component)]
+pub(crate) struct HtmlSliderParts {
+	track: Entity,
+	fill: Entity,
+	thumb: Entity,
+}
+
+const THUMB_SIZE: f32 = 16.0;
+
+/// `<slider min="0" max="100" step="5" value="50" orientation="vertical">`, a track with a
+/// draggable thumb.
+///
+/// Drawn with the classes `slider-track`, `slider-fill` and `slider-thumb` for styling.
+#[derive(Default)]
+pub(crate) struct SliderTag;
+
+impl HtmlTagHandler for SliderTag {
+	#[allow(clippy::too_many_lines)]
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		let number = |name: &str, default: f32| {
+			element.attribute(name).map_or(default, |value| {
+				value.trim().parse().unwrap_or_else(|_| {
+					warn!("invalid <slider> {name} `{value}`, expected a number");
+					default
+				})
+			})
+		};
+		let vertical = match element.attribute("orientation") {
+			None | Some("horizontal") => false,
+			Some("vertical") => true,
+			Some(other) => {
+				warn!("unknown <slider> orientation `{other}`, expected horizontal or vertical");
+				false
+			}
+		};
+		let min = number("min", 0.0);
+		let max = number("max", 100.0);
+		let step = if element.attribute("step") == Some("any") {
+			0.0
+		} else {
+			number("step", 1.0).max(0.0)
+		};
+		let mut slider = HtmlSlider {
+			min,
+			max,
+			step,
+			value: min,
+			vertical,
+		};
+		slider.set_value(number("value", min + (max - min) / 2.0));
+
+		let parts = entity.world_scope(|world| {
+			let fill = world
+				.spawn((
+					if vertical {
+						Node {
+							position_type: PositionType::Absolute,
+							bottom: Val::Px(0.0),
+							width: Val::Percent(100.0),
+							..default()
+						}
+					} else {
+						Node {
+							position_type: PositionType::Absolute,
+							left: Val::Px(0.0),
+							height: Val::Percent(100.0),
+							..default()
+						}
+					},
+					BackgroundColor(Color::WHITE),
+					ClassList::new("slider-fill"),
+					Pickable::IGNORE,
+				))
+				.id();
+			let thumb = world
+				.spawn((
+					if vertical {
+						Node {
+							position_type: PositionType::Absolute,
+							left: Val::Percent(50.0),
+							margin: UiRect::new(
+								Val::Px(-THUMB_SIZE / 2.0),
+								Val::ZERO,
+								Val::ZERO,
+								Val::Px(-THUMB_SIZE / 2.0),
+							),
+							width: Val::Px(THUMB_SIZE),
+							height: Val::Px(THUMB_SIZE),
+							border_radius: BorderRadius::MAX,
+							..default()
+						}
+					} else {
+						Node {
+							position_type: PositionType::Absolute,
+							top: Val::Percent(50.0),
+							margin: UiRect::new(
+								Val::Px(-THUMB_SIZE / 2.0),
+								Val::ZERO,
+								Val::Px(-THUMB_SIZE / 2.0),
+								Val::ZERO,
+							),
+							width: Val::Px(THUMB_SIZE),
+							height: Val::Px(THUMB_SIZE),
+							border_radius: BorderRadius::MAX,
+							..default()
+						}
+					},
+					BackgroundColor(Color::WHITE),
+					ClassList::new("slider-thumb"),
+					Pickable::IGNORE,
+				))
+				.id();
+			let track = world
+				.spawn((
+					if vertical {
+						Node {
+							width: Val::Px(4.0),
+							height: Val::Percent(100.0),
+							..default()
+						}
+					} else {
+						Node {
+							width: Val::Percent(100.0),
+							height: Val::Px(4.0),
+							..default()
+						}
+					},
+					BackgroundColor(Color::srgb(0.4, 0.4, 0.4)),
+					ClassList::new("slider-track"),
+					Pickable::IGNORE,
+				))
+				.add_children(&[fill, thumb])
+				.id();
+			HtmlSliderParts { track, fill, thumb }
+		});
+
+		let padding = Val::Px(THUMB_SIZE / 2.0);
+		entity.add_child(parts.track).insert((
+			if vertical {
+				Node {
+					width: Val::Px(THUMB_SIZE),
+					height: Val::Px(160.0),
+					padding: UiRect::vertical(padding),
+					justify_content: JustifyContent::Center,
+					..default()
+				}
+			} else {
+				Node {
+					width: Val::Px(160.0),
+					height: Val::Px(THUMB_SIZE),
+					padding: UiRect::horizontal(padding),
+					align_items: AlignItems::Center,
+					..default()
+				}
+			},
+			slider,
+			parts,
+			AutoDirectionalNavigation::default(),
+			Pickable {
+				is_hoverable: true,
+				should_block_lower: true,
+			},
+		));
+		if element.attribute("disabled").is_some() {
+			entity.insert(InteractionDisabled);
+		}
+	}
+
+	fn spawn_children(&self) -> bool {
+		false
+	}
+}
+
+/// Moves a `<slider>` to the value under the pointer, reporting it if it changed.
+fn slide_to_pointer(
+	entity: Entity,
+	position: Vec2,
+	sliders: &mut Query<(&mut HtmlSlider, &HtmlSliderParts, Has<InteractionDisabled>)>,
+	tracks: &Query<(&ComputedNode, &UiGlobalTransform)>,
+	changed: &mut MessageWriter<HtmlValueChanged>,
+) {
+	let Ok((mut slider, parts, false)) = sliders.get_mut(entity) else {
+		return;
+	};
+	let Ok((node, transform)) = tracks.get(parts.track) else {
+		return;
+	};
+	let Some(inverse) = transform.try_inverse() else {
+		return;
+	};
+	let local = inverse.transform_point2(position / node.inverse_scale_factor()) + node.size / 2.0;
+	let fraction = if slider.vertical {
+		1.0 - local.y / node.size.y
+	} else {
+		local.x / node.size.x
+	};
+	if !fraction.is_finite() {
+		return;
+	}
+	let value = slider.min + fraction.clamp(0.0, 1.0) * (slider.max - slider.min);
+	set_and_report(entity, &mut slider, value, changed);
+}
+
+fn set_and_report(
+	entity: Entity,
+	slider: &mut Mut<HtmlSlider>,
+	value: f32,
+	changed: &mut MessageWriter<HtmlValueChanged>,
+) {
+	let previous = slider.value;
+	slider.set_value(value);
+	if (slider.value - previous).abs() > f32::EPSILON {
+		changed.write(HtmlValueChanged {
+			entity,
+			value: HtmlValue::Number(f64::from(slider.value)),
+		});
+	}
+}
+
+/// Focuses a pressed `<slider>` and jumps its thumb to the pointer.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_slider_press(
+	press: On<Pointer<Press>>,
+	mut focus: ResMut<InputFocus>,
+	mut sliders: Query<(&mut HtmlSlider, &HtmlSliderParts, Has<InteractionDisabled>)>,
+	tracks: Query<(&ComputedNode, &UiGlobalTransform)>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	if !sliders.contains(press.entity) {
+		return;
+	}
+	focus.set(press.entity);
+	slide_to_pointer(
+		press.entity,
+		press.pointer_location.position,
+		&mut sliders,
+		&tracks,
+		&mut changed,
+	);
+}
+
+/// Drags the thumb of a `<slider>`.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_slider_drag(
+	drag: On<Pointer<Drag>>,
+	mut sliders: Query<(&mut HtmlSlider, &HtmlSliderParts, Has<InteractionDisabled>)>,
+	tracks: Query<(&ComputedNode, &UiGlobalTransform)>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	slide_to_pointer(
+		drag.entity,
+		drag.pointer_location.position,
+		&mut sliders,
+		&tracks,
+		&mut changed,
+	);
+}
+
+/// Steps the focused `<slider>` with the arrow keys or D-pad: left and right for a horizontal
+/// slider, down and up for a vertical one.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn html_slider_keyboard(
+	mut step: StepInput,
+	focus: Res<InputFocus>,
+	mut sliders: Query<(&mut HtmlSlider, Has<InteractionDisabled>)>,
+	mut changed: MessageWriter<HtmlValueChanged>,
+) {
+	let step = step.just_stepped();
+	let Some(entity) = focus.get() else {
+		return;
+	};
+	let Ok((mut slider, false)) = sliders.get_mut(entity) else {
+		return;
+	};
+	let direction = if slider.vertical { -step.y } else { step.x };
+	if direction != 0 {
+		#[allow(clippy::cast_precision_loss)]
+		let value = slider.value + direction as f32 * slider.key_step();
+		set_and_report(entity, &mut slider, value, &mut changed);
+	}
+}
+
+/// Moves the fill and thumb of each changed `<slider>` to match its value.
+pub(crate) fn html_slider_update_display(
+	sliders: Query<(&HtmlSlider, &HtmlSliderParts), Changed<HtmlSlider>>,
+	mut nodes: Query<&mut Node>,
+) {
+	for (slider, parts) in &sliders {
+		let percent = Val::Percent(slider.fraction() * 100.0);
+		if let Ok(mut fill) = nodes.get_mut(parts.fill) {
+			if slider.vertical {
+				fill.height = percent;
+			} else {
+				fill.width = percent;
+			}
+		}
+		if let Ok(mut thumb) = nodes.get_mut(parts.thumb) {
+			if slider.vertical {
+				thumb.bottom = percent;
+			} else {
+				thumb.left = percent;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod slider_tests {
+	use bevy::{
+		input::keyboard::Key,
+		input_focus::InputFocus,
+		picking::events::{Drag, Press},
+		prelude::*,
+		ui::InteractionDisabled,
+	};
+
+	use super::{
+		HtmlSlider, HtmlSliderParts, html_slider_drag, html_slider_keyboard, html_slider_press,
+		html_slider_update_display,
+	};
+	use crate::{
+		build::build_tests::{changed_values, hit, pointer, press_key, spawn_form},
+		data::HtmlValue,
+	};
+
+	#[test]
+	fn test_set_value() {
+		let mut slider = HtmlSlider {
+			min: 0.0,
+			max: 100.0,
+			step: 5.0,
+			value: 0.0,
+			vertical: false,
+		};
+		slider.set_value(52.4);
+		assert_eq!(slider.value, 50.0);
+		slider.set_value(130.0);
+		assert_eq!(slider.value, 100.0);
+		assert_eq!(slider.fraction(), 1.0);
+
+		slider.step = 0.0;
+		slider.set_value(12.3);
+		assert_eq!(slider.value, 12.3);
+
+		// The last step within the range is 9, which values past it round down to.
+		slider.max = 10.0;
+		slider.step = 3.0;
+		slider.set_value(11.0);
+		assert_eq!(slider.value, 9.0);
+		slider.set_value(10.0);
+		assert_eq!(slider.value, 9.0);
+		slider.set_value(-1.0);
+		assert_eq!(slider.value, 0.0);
+	}
+
+	/// Spawns `source` with the slider observers and systems, returning its sliders.
+	fn spawn_sliders(source: &str) -> (App, Vec<Entity>) {
+		let (mut app, root) = spawn_form(source);
+		app.add_observer(html_slider_press)
+			.add_observer(html_slider_drag)
+			.add_systems(
+				Update,
+				(html_slider_keyboard, html_slider_update_display).chain(),
+			);
+		app.update();
+		let sliders = app.world().get::<Children>(root).unwrap().to_vec();
+		(app, sliders)
+	}
+
+	fn value(app: &App, slider: Entity) -> f32 {
+		app.world().get::<HtmlSlider>(slider).unwrap().value
+	}
+
+	/// The point `fraction` of the way along the track of `slider`, from its minimum.
+	fn track_point(app: &App, slider: Entity, fraction: f32) -> Vec2 {
+		let world = app.world();
+		let track = world.get::<HtmlSliderParts>(slider).unwrap().track;
+		let size = world.get::<ComputedNode>(track).unwrap().size();
+		let center = world.get::<UiGlobalTransform>(track).unwrap().translation;
+		if world.get::<HtmlSlider>(slider).unwrap().vertical {
+			center + Vec2::new(0.0, (0.5 - fraction) * size.y)
+		} else {
+			center + Vec2::new((fraction - 0.5) * size.x, 0.0)
+		}
+	}
+
+	/// Triggers `event` on `slider` with the pointer `fraction` of the way along its track.
+	fn trigger_at<E: std::fmt::Debug + Clone + Reflect>(
+		app: &mut App,
+		slider: Entity,
+		fraction: f32,
+		event: E,
+	) {
+		let mut event = pointer(slider, event);
+		event.pointer_location.position = track_point(app, slider, fraction);
+		app.world_mut().trigger(event);
+		app.world_mut().flush();
+	}
+
+	fn press_at(app: &mut App, slider: Entity, fraction: f32) {
+		let press = Press {
+			button: PointerButton::Primary,
+			hit: hit(),
+		};
+		trigger_at(app, slider, fraction, press);
+	}
+
+	fn drag_to(app: &mut App, slider: Entity, fraction: f32) {
+		let drag = Drag {
+			button: PointerButton::Primary,
+			distance: Vec2::ZERO,
+			delta: Vec2::ZERO,
+		};
+		trigger_at(app, slider, fraction, drag);
+	}
+
+	/// Pressing a slider focuses it and jumps its value to the pointer, which dragging then follows.
+	#[test]
+	fn test_slider_pointer() {
+		let (mut app, sliders) =
+			spawn_sliders(r#"<slider /><slider orientation="vertical" /><slider disabled />"#);
+
+		press_at(&mut app, sliders[0], 0.25);
+		assert_eq!(value(&app, sliders[0]), 25.0);
+		assert_eq!(app.world().resource::<InputFocus>().get(), Some(sliders[0]));
+		let changed = changed_values(&app);
+		assert_eq!(changed.len(), 1);
+		assert_eq!(changed[0].entity, sliders[0]);
+		assert_eq!(changed[0].value, HtmlValue::Number(25.0));
+
+		drag_to(&mut app, sliders[0], 0.75);
+		assert_eq!(value(&app, sliders[0]), 75.0);
+		app.update();
+		let thumb = app
+			.world()
+			.get::<HtmlSliderParts>(sliders[0])
+			.unwrap()
+			.thumb;
+		assert_eq!(
+			app.world().get::<Node>(thumb).unwrap().left,
+			Val::Percent(75.0)
+		);
+
+		// A vertical slider runs bottom to top.
+		press_at(&mut app, sliders[1], 0.25);
+		assert_eq!(value(&app, sliders[1]), 25.0);
+		drag_to(&mut app, sliders[1], 1.5);
+		assert_eq!(value(&app, sliders[1]), 100.0);
+
+		app.update();
+		press_at(&mut app, sliders[2], 0.25);
+		drag_to(&mut app, sliders[2], 0.75);
+		assert!(
+			app.world()
+				.entity(sliders[2])
+				.contains::<InteractionDisabled>()
+		);
+		assert_eq!(value(&app, sliders[2]), 50.0);
+		assert!(changed_values(&app).is_empty());
+	}
+
+	/// The arrow keys and D-pad step the focused slider along its orientation, within its range.
+	#[test]
+	fn test_slider_step() {
+		let (mut app, sliders) = spawn_sliders(
+			r#"<slider max="10" step="3" value="6" /><slider orientation="vertical" /><slider disabled />"#,
+		);
+		let step = |app: &mut App, slider: Entity, key: Key| {
+			app.world_mut().resource_mut::<InputFocus>().set(slider);
+			press_key(app, key);
+			app.update();
+			(value(app, slider), changed_values(app).len())
+		};
+
+		assert_eq!(step(&mut app, sliders[0], Key::ArrowRight), (9.0, 1));
+		assert_eq!(step(&mut app, sliders[0], Key::ArrowRight), (9.0, 0));
+		assert_eq!(step(&mut app, sliders[0], Key::ArrowLeft), (6.0, 1));
+		assert_eq!(step(&mut app, sliders[0], Key::ArrowUp), (6.0, 0));
+
+		assert_eq!(step(&mut app, sliders[1], Key::ArrowUp), (51.0, 1));
+		assert_eq!(step(&mut app, sliders[1], Key::ArrowDown), (50.0, 1));
+		assert_eq!(step(&mut app, sliders[1], Key::ArrowRight), (50.0, 0));
+
+		assert_eq!(step(&mut app, sliders[2], Key::ArrowRight), (50.0, 0));
+
+		let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+		app.world_mut().resource_mut::<InputFocus>().set(sliders[1]);
+		app.world_mut()
+			.get_mut::<Gamepad>(gamepad)
+			.unwrap()
+			.digital_mut()
+			.press(GamepadButton::DPadUp);
+		app.update();
+		assert_eq!(value(&app, sliders[1]), 51.0);
+		assert_eq!(changed_values(&app).len(), 1);
+	}
+}
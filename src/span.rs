@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use bevy_flair::prelude::*;
+
+use crate::{
+	ast::HtmlElement, bindings::HtmlTextBinding, direction::apply_direction,
+	locale::HtmlTranslatedText, tags::HtmlTagHandler,
+};
+
+/// `<span>`, `<b>`, `<i>` and `<color value="#e04040">`, runs of text within the text of their
+/// parent, such as `<label>Press <b>A</b> to jump</label>`.
+///
+/// Each becomes a `TextSpan` that can be selected by its tag name and classes. `<b>` is bold and
+/// `<color>` sets the CSS `color`, while `<i>` is left to stylesheets, which can give it an italic
+/// font.
+#[derive(Default)]
+pub(crate) struct SpanTag;
+
+impl HtmlTagHandler for SpanTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		// Spans are only drawn inside a `Text`, which the parent becomes if it is not text already.
+		// Next to node children, such as in a row of text and icons, that is the run of text before
+		// the span.
+		if let Some(parent) = entity.get::<ChildOf>().map(ChildOf::parent) {
+			let id = entity.id();
+			entity.world_scope(|world| {
+				if needs_text_run(world, parent) {
+					let run = text_run(world, parent, id);
+					world.entity_mut(id).insert(ChildOf(run));
+					return;
+				}
+				let parent = world.entity(parent);
+				if !parent.contains::<Text>() && !parent.contains::<TextSpan>() {
+					world.entity_mut(parent.id()).insert(Text::default());
+				}
+			});
+		}
+
+		let type_name = match element.tag.as_str() {
+			"b" => "b",
+			"i" => "i",
+			"color" => "color",
+			_ => "span",
+		};
+		entity.insert((TextSpan::default(), TypeName(type_name)));
+
+		match type_name {
+			"b" => {
+				// Spans do not inherit the font of their text, so keep its family and size, and
+				// follow them with `html_span_bold_font`.
+				let text_font = inherited_font(entity).unwrap_or_default();
+				let mut font = text_font.clone();
+				font.weight = FontWeight::BOLD;
+				entity.insert((font, HtmlBoldFont(text_font)));
+			}
+			"color" => match element.attribute("value") {
+				Some(color) if !color.trim().is_empty() => {
+					entity.insert(InlineStyle::from_iter([("color", color.trim().to_owned())]));
+				}
+				_ => warn!("<color> without a value"),
+			},
+			_ => {}
+		}
+	}
+
+	fn inline(&self) -> bool {
+		true
+	}
+}
+
+/// The font of the nearest ancestor of `entity` that has one, such as the `Text` a span is in.
+fn inherited_font(entity: &EntityWorldMut) -> Option<TextFont> {
+	let world = entity.world();
+	let mut ancestor = entity.get::<ChildOf>().map(ChildOf::parent);
+	while let Some(id) = ancestor {
+		let ancestor_ref = world.entity(id);
+		if let Some(font) = ancestor_ref.get::<TextFont>() {
+			return Some(font.clone());
+		}
+		ancestor = ancestor_ref.get::<ChildOf>().map(ChildOf::parent);
+	}
+	None
+}
+
+/// The font of the text a `<b>` is in, as it was last copied into the `<b>`.
+#[derive(Component)]
+pub(crate) struct HtmlBoldFont(TextFont);
+
+/// Copies changes to the font of the text each `<b>` is in, such as a new size from a stylesheet,
+/// into the `<b>`. Its family, size or smoothing are left alone when they differ from what was last
+/// copied, as they do when a stylesheet sets them on the `<b>` itself.
+#[allow(clippy::type_complexity)]
+pub(crate) fn html_span_bold_font(
+	spans: Query<(Entity, &ChildOf), With<HtmlBoldFont>>,
+	mut fonts: ParamSet<(Query<&TextFont>, Query<(&mut TextFont, &mut HtmlBoldFont)>)>,
+) {
+	for (entity, child_of) in &spans {
+		let Ok(text_font) = fonts.p0().get(child_of.parent()).cloned() else {
+			continue;
+		};
+		let mut q_bold = fonts.p1();
+		let Ok((mut font, mut copied)) = q_bold.get_mut(entity) else {
+			continue;
+		};
+		let copied = &mut copied.0;
+		let same_size = |a: &TextFont, b: &TextFont| a.font_size.to_bits() == b.font_size.to_bits();
+		if text_font.font == copied.font
+			&& same_size(&text_font, copied)
+			&& text_font.font_smoothing == copied.font_smoothing
+		{
+			continue;
+		}
+		if font.font == copied.font {
+			font.font = text_font.font.clone();
+		}
+		if same_size(&font, copied) {
+			font.font_size = text_font.font_size;
+		}
+		if font.font_smoothing == copied.font_smoothing {
+			font.font_smoothing = text_font.font_smoothing;
+		}
+		*copied = text_font;
+	}
+}
+
+/// Marks an element whose text was split around an `<icon>` or `<key>`, so that it lays out as a
+/// wrapping row of text runs and icons instead of a single `Text`.
+#[derive(Component)]
+pub(crate) struct HtmlTextRow;
+
+/// A run of text next to node children, such as within an [`HtmlTextRow`].
+#[derive(Component)]
+pub(crate) struct HtmlTextRun;
+
+/// Turns the parent of the inline `entity`, such as an `<icon>`, into an [`HtmlTextRow`] if it is
+/// not one already. Any text and spans the parent has so far move into a run before `entity`.
+pub(crate) fn join_text_row(entity: &mut EntityWorldMut) {
+	let Some(parent) = entity.get::<ChildOf>().map(ChildOf::parent) else {
+		return;
+	};
+	let id = entity.id();
+	entity.world_scope(|world| {
+		let mut row = world.entity_mut(parent);
+		if row.contains::<HtmlTextRow>() {
+			return;
+		}
+		if row.contains::<TextSpan>() {
+			warn!("icons cannot be placed inside a span");
+			return;
+		}
+		let text = row.take::<Text>();
+		let binding = row.take::<HtmlTextBinding>();
+		let translated = row.take::<HtmlTranslatedText>();
+		row.insert(HtmlTextRow);
+		if let Some(mut node) = row.get_mut::<Node>() {
+			node.flex_wrap = FlexWrap::Wrap;
+			node.align_items = AlignItems::Center;
+		}
+
+		let Some(text) = text else {
+			return;
+		};
+		let spans: Vec<Entity> = world
+			.entity(parent)
+			.get::<Children>()
+			.map(|children| children.iter().filter(|child| *child != id).collect())
+			.unwrap_or_default();
+		let mut run = world.spawn((
+			text,
+			HtmlTextRun,
+			NodeStyleSheet::Inherited,
+			Pickable::IGNORE,
+		));
+		if let Some(binding) = binding {
+			run.insert(binding);
+		}
+		if let Some(translated) = translated {
+			run.insert(translated);
+		}
+		let run = run.add_children(&spans).id();
+		world.entity_mut(parent).insert_children(0, &[run]);
+		apply_direction(&mut world.entity_mut(run));
+	});
+}
+
+/// Marks an element that spawns node children ahead of its content, such as the box of a
+/// `<checkbox>`, since bevy does not lay out the `Text` of a node that has node children.
+#[derive(Component)]
+pub(crate) struct HtmlTextAfterParts;
+
+/// Whether text added to `parent` goes into a run of its own, as it does in an [`HtmlTextRow`] or
+/// after the parts of an [`HtmlTextAfterParts`].
+pub(crate) fn needs_text_run(world: &World, parent: Entity) -> bool {
+	let parent = world.entity(parent);
+	parent.contains::<HtmlTextRow>() || parent.contains::<HtmlTextAfterParts>()
+}
+
+/// The run of text at the end of `row` that text and spans after a node child go into, which is added
+/// if the last child other than `except` is not one.
+pub(crate) fn text_run(world: &mut World, row: Entity, except: Entity) -> Entity {
+	let last = world
+		.entity(row)
+		.get::<Children>()
+		.and_then(|children| children.iter().rfind(|child| *child != except));
+	if let Some(last) = last
+		&& world.entity(last).contains::<HtmlTextRun>()
+	{
+		return last;
+	}
+	let mut run = world.spawn((
+		Text::default(),
+		HtmlTextRun,
+		NodeStyleSheet::Inherited,
+		Pickable::IGNORE,
+		ChildOf(row),
+	));
+	apply_direction(&mut run);
+	run.id()
+}
+
+#[cfg(test)]
+mod span_tests {
+	use bevy::prelude::*;
+
+	use super::html_span_bold_font;
+	use crate::{
+		build::{build_tests::spawn_laid_out, spawn_node},
+		parser::parse_htmlish,
+	};
+
+	/// `<b>` keeps the size of the text it is in as that changes, making it bold, unless its own
+	/// size was set.
+	#[test]
+	fn test_bold_font() {
+		let (mut app, root) = spawn_laid_out("");
+		app.add_systems(Update, html_span_bold_font);
+		let world = app.world_mut();
+		let label = world
+			.spawn((
+				Text::default(),
+				TextFont {
+					font_size: 32.0,
+					..default()
+				},
+				ChildOf(root),
+			))
+			.id();
+		for node in &parse_htmlish(&"<b>Bold</b>".into()).unwrap() {
+			spawn_node(world, label, node);
+		}
+		let bold = world.get::<Children>(label).unwrap()[0];
+		let font = world.get::<TextFont>(bold).unwrap();
+		assert_eq!(font.font_size, 32.0);
+		assert_eq!(font.weight, FontWeight::BOLD);
+
+		let font_size = |app: &App| app.world().get::<TextFont>(bold).unwrap().font_size;
+		app.world_mut()
+			.get_mut::<TextFont>(label)
+			.unwrap()
+			.font_size = 40.0;
+		app.update();
+		assert_eq!(font_size(&app), 40.0);
+		assert_eq!(
+			app.world().get::<TextFont>(bold).unwrap().weight,
+			FontWeight::BOLD
+		);
+
+		app.world_mut().get_mut::<TextFont>(bold).unwrap().font_size = 50.0;
+		app.world_mut()
+			.get_mut::<TextFont>(label)
+			.unwrap()
+			.font_size = 44.0;
+		app.update();
+		assert_eq!(font_size(&app), 50.0);
+	}
+}
@@ -0,0 +1,313 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, RwLock},
+};
+
+use bevy::{prelude::*, ui::auto_directional_navigation::AutoDirectionalNavigation};
+
+use crate::{
+	ast::HtmlElement,
+	checkbox::{CheckboxTag, ToggleTag},
+	grid::GridTag,
+	icon::{IconTag, KeyTag},
+	img::{ImgTag, PanelTag},
+	input::{InputTag, TextareaTag},
+	progress::ProgressTag,
+	radio::{RadioGroupTag, RadioTag},
+	scroll::ScrollTag,
+	select::{OptionTag, SelectTag},
+	slider::SliderTag,
+	span::SpanTag,
+};
+
+/// Spawns the components for one kind of tag.
+///
+/// Every tag, including the built-in ones, is spawned through a handler registered with
+/// [`HtmlUiAppExt::register_html_tag`].
+pub trait HtmlTagHandler: Send + Sync + 'static {
+	/// Inserts the tag's components on `entity`.
+	///
+	/// `entity` already has a `Node` (unless the tag is [`inline`](Self::inline)), its stylesheet,
+	/// its parent, and the components for the `id`, `class` and `autofocus` attributes. The children
+	/// of `element` are spawned after this returns.
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement);
+
+	/// Whether the children of the element are spawned after [`insert`](Self::insert). Tags that
+	/// read their children as data instead, like `<textarea>`, return `false`.
+	fn spawn_children(&self) -> bool {
+		true
+	}
+
+	/// Whether the element is a run of text within the text of its parent, like `<b>`, spawned
+	/// without a `Node` so that it can be a `TextSpan`. Layout and background image attributes do
+	/// not apply to it.
+	fn inline(&self) -> bool {
+		false
+	}
+
+	/// The attributes the tag reads itself that are also layout attributes, like a `width` meaning
+	/// something else than the width of its `Node`. They are left out when applying layout
+	/// attributes.
+	fn attributes(&self) -> &[&str] {
+		&[]
+	}
+}
+
+/// The handlers for every tag name that can be spawned.
+#[derive(Default, Resource)]
+pub struct HtmlTagRegistry {
+	handlers: HashMap<String, Arc<dyn HtmlTagHandler>>,
+	names: HtmlTagNames,
+}
+
+impl HtmlTagRegistry {
+	pub fn register(&mut self, name: impl Into<String>, handler: impl HtmlTagHandler) {
+		let name = name.into();
+		self.names.insert(name.clone());
+		self.handlers.insert(name, Arc::new(handler));
+	}
+
+	/// A registry of only the built-in tags, for reading documents outside of an app.
+	#[cfg(feature = "codegen")]
+	pub(crate) fn builtin() -> Self {
+		let mut registry = Self::default();
+		register_builtin_handlers(&mut registry);
+		registry
+	}
+
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<Arc<dyn HtmlTagHandler>> {
+		self.handlers.get(name).cloned()
+	}
+
+	pub(crate) fn names(&self) -> HtmlTagNames {
+		self.names.clone()
+	}
+}
+
+/// The names in an [`HtmlTagRegistry`], shared with the loader so that documents using a tag that
+/// is neither registered nor a template fail to load. Tags registered later are seen as well.
+#[derive(Clone, Default)]
+pub(crate) struct HtmlTagNames(Arc<RwLock<HashSet<String>>>);
+
+impl HtmlTagNames {
+	pub(crate) fn insert(&self, name: impl Into<String>) {
+		if let Ok(mut names) = self.0.write() {
+			names.insert(name.into());
+		}
+	}
+
+	pub(crate) fn contains(&self, name: &str) -> bool {
+		self.0.read().is_ok_and(|names| names.contains(name))
+	}
+}
+
+pub trait HtmlUiAppExt {
+	/// Registers `T` as the handler for `<name>` tags, replacing any previous handler.
+	fn register_html_tag<T: HtmlTagHandler + FromWorld>(&mut self, name: &str) -> &mut Self;
+}
+
+impl HtmlUiAppExt for App {
+	fn register_html_tag<T: HtmlTagHandler + FromWorld>(&mut self, name: &str) -> &mut Self {
+		let handler = T::from_world(self.world_mut());
+		self.world_mut()
+			.get_resource_or_init::<HtmlTagRegistry>()
+			.register(name, handler);
+		self
+	}
+}
+
+pub(crate) fn register_builtin_tags(app: &mut App) {
+	register_builtin_handlers(&mut app.world_mut().get_resource_or_init::<HtmlTagRegistry>());
+}
+
+fn register_builtin_handlers(registry: &mut HtmlTagRegistry) {
+	registry.register("ui", UiTag);
+	registry.register("vbox", VBoxTag);
+	registry.register("hbox", HBoxTag);
+	registry.register("node", NodeTag);
+	registry.register("label", LabelTag);
+	registry.register("button", ButtonTag);
+	registry.register("spacer", SpacerTag);
+	registry.register("panel", PanelTag);
+	registry.register("img", ImgTag);
+	registry.register("input", InputTag);
+	registry.register("textarea", TextareaTag);
+	registry.register("checkbox", CheckboxTag);
+	registry.register("toggle", ToggleTag);
+	registry.register("radio-group", RadioGroupTag);
+	registry.register("radio", RadioTag);
+	registry.register("slider", SliderTag);
+	registry.register("select", SelectTag);
+	registry.register("option", OptionTag);
+	registry.register("progress", ProgressTag);
+	registry.register("scroll", ScrollTag);
+	registry.register("grid", GridTag);
+	registry.register("span", SpanTag);
+	registry.register("b", SpanTag);
+	registry.register("i", SpanTag);
+	registry.register("color", SpanTag);
+	registry.register("icon", IconTag);
+	registry.register("key", KeyTag);
+}
+
+#[derive(Default)]
+pub(crate) struct UiTag;
+
+impl HtmlTagHandler for UiTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		entity.insert((
+			Node {
+				position_type: PositionType::Absolute,
+				display: Display::Flex,
+				flex_direction: FlexDirection::Column,
+				row_gap: element.gap,
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				..default()
+			},
+			Pickable::IGNORE,
+		));
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct VBoxTag;
+
+impl HtmlTagHandler for VBoxTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		entity.insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: FlexDirection::Column,
+				row_gap: element.gap,
+				..default()
+			},
+			Pickable::IGNORE,
+		));
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct HBoxTag;
+
+impl HtmlTagHandler for HBoxTag {
+	fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+		entity.insert((
+			Node {
+				display: Display::Flex,
+				flex_direction: FlexDirection::Row,
+				column_gap: element.gap,
+				..default()
+			},
+			Pickable::IGNORE,
+		));
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct NodeTag;
+
+impl HtmlTagHandler for NodeTag {
+	fn insert(&self, _entity: &mut EntityWorldMut, _element: &HtmlElement) {}
+}
+
+#[derive(Default)]
+pub(crate) struct LabelTag;
+
+impl HtmlTagHandler for LabelTag {
+	fn insert(&self, entity: &mut EntityWorldMut, _element: &HtmlElement) {
+		entity.insert(Pickable::IGNORE);
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct ButtonTag;
+
+impl HtmlTagHandler for ButtonTag {
+	fn insert(&self, entity: &mut EntityWorldMut, _element: &HtmlElement) {
+		entity.insert((
+			Button,
+			AutoDirectionalNavigation::default(),
+			Pickable {
+				is_hoverable: true,
+				should_block_lower: true,
+			},
+		));
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct SpacerTag;
+
+impl HtmlTagHandler for SpacerTag {
+	fn insert(&self, entity: &mut EntityWorldMut, _element: &HtmlElement) {
+		entity.insert((
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				..default()
+			},
+			Pickable::IGNORE,
+		));
+	}
+}
+
+#[cfg(test)]
+mod tags_tests {
+	use bevy::prelude::*;
+
+	use super::{HtmlTagHandler, HtmlUiAppExt};
+	use crate::{
+		asset::HtmlUiAsset,
+		ast::HtmlElement,
+		build::spawn_node,
+		loader::loader_tests::{document_app, wait_for_load},
+	};
+
+	#[derive(Component)]
+	struct HealthBar(f32);
+
+	#[derive(Default)]
+	struct HealthBarTag;
+
+	impl HtmlTagHandler for HealthBarTag {
+		fn insert(&self, entity: &mut EntityWorldMut, element: &HtmlElement) {
+			let value = element
+				.attribute("value")
+				.and_then(|value| value.parse().ok())
+				.unwrap_or(0.0);
+			entity.insert(HealthBar(value));
+		}
+	}
+
+	/// Tags registered after the loader was created are accepted by it and spawned by their handler.
+	#[test]
+	fn test_register_html_tag() {
+		let files = [("hud.html", r#"<ui><health-bar value="75" /></ui>"#)];
+		let mut app = document_app(&files);
+		app.register_html_tag::<HealthBarTag>("health-bar");
+
+		let handle = app
+			.world()
+			.resource::<AssetServer>()
+			.load::<HtmlUiAsset>("hud.html");
+		assert!(wait_for_load(&mut app, &handle));
+
+		let ast = app
+			.world()
+			.resource::<Assets<HtmlUiAsset>>()
+			.get(&handle)
+			.unwrap()
+			.ast
+			.clone();
+		let world = app.world_mut();
+		let root = world.spawn(Node::default()).id();
+		for node in &ast {
+			spawn_node(world, root, node);
+		}
+		let mut q_bars = world.query::<(&HealthBar, &Node)>();
+		let (bar, _) = q_bars.single(world).unwrap();
+		assert_eq!(bar.0, 75.0);
+	}
+}
@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use bevy::ui::Val;
+
+use crate::{
+	ast::{HtmlEach, HtmlElement, HtmlNode, HtmlTag},
+	error::HtmlUiError,
+};
+
+/// How many templates may be nested inside each other before expansion gives up, which also
+/// catches templates that use themselves.
+const MAX_TEMPLATE_DEPTH: usize = 32;
+
+#[derive(PartialEq)]
+struct HtmlTemplate {
+	params: Vec<String>,
+	body: Vec<HtmlNode>,
+}
+
+/// Removes every `<template name="...">` from `nodes` and replaces each use of a template's name as
+/// a tag with the template's body, substituting `{{ param }}`s and filling `<slot />` with the
+/// children of the tag. Tags that match no template are left for the tag registry.
+///
+/// Returns the expanded nodes and the removed `<template>` elements, so documents that include
+/// this one can use its templates too. Defining two different templates with the same name is an
+/// error, while the same template may be reached through several includes.
+pub(crate) fn expand_templates(
+	nodes: Vec<HtmlNode>,
+) -> Result<(Vec<HtmlNode>, Vec<HtmlNode>), HtmlUiError> {
+	let mut definitions = Vec::new();
+	let nodes = extract_templates(nodes, &mut definitions);
+
+	let mut templates = HashMap::new();
+	for definition in &definitions {
+		let name = definition
+			.attribute("name")
+			.ok_or_else(|| HtmlUiError::ParseError("<template> without a name".into()))?
+			.to_owned();
+		let params = definition
+			.attribute("params")
+			.unwrap_or_default()
+			.split_whitespace()
+			.map(str::to_owned)
+			.collect();
+		let template = HtmlTemplate {
+			params,
+			body: definition.children.clone(),
+		};
+		if templates
+			.get(&name)
+			.is_some_and(|defined| *defined != template)
+		{
+			return Err(HtmlUiError::ParseError(format!(
+				"template `{name}` is defined more than once"
+			)));
+		}
+		templates.insert(name, template);
+	}
+
+	let nodes = expand_nodes(nodes, &templates, 0)?;
+	let definitions = definitions.into_iter().map(HtmlNode::Element).collect();
+	Ok((nodes, definitions))
+}
+
+fn extract_templates(nodes: Vec<HtmlNode>, definitions: &mut Vec<HtmlElement>) -> Vec<HtmlNode> {
+	let mut remaining = Vec::with_capacity(nodes.len());
+	for node in nodes {
+		match node {
+			HtmlNode::Element(mut element) if element.tag == HtmlTag::Template => {
+				element.children = extract_templates(element.children, definitions);
+				definitions.push(element);
+			}
+			HtmlNode::Element(mut element) => {
+				element.children = extract_templates(element.children, definitions);
+				remaining.push(HtmlNode::Element(element));
+			}
+			HtmlNode::Text(_) => remaining.push(node),
+		}
+	}
+	remaining
+}
+
+fn expand_nodes(
+	nodes: Vec<HtmlNode>,
+	templates: &HashMap<String, HtmlTemplate>,
+	depth: usize,
+) -> Result<Vec<HtmlNode>, HtmlUiError> {
+	let mut expanded = Vec::with_capacity(nodes.len());
+	for node in nodes {
+		match node {
+			HtmlNode::Element(element) => {
+				if let HtmlTag::Named(name) = &element.tag
+					&& let Some(template) = templates.get(name)
+				{
+					if depth >= MAX_TEMPLATE_DEPTH {
+						return Err(HtmlUiError::ParseError(format!(
+							"template `{name}` is nested too deeply"
+						)));
+					}
+					expanded.extend(instantiate(&element, template, templates, depth)?);
+				} else {
+					let mut element = element;
+					element.children = expand_nodes(element.children, templates, depth)?;
+					expanded.push(HtmlNode::Element(element));
+				}
+			}
+			HtmlNode::Text(_) => expanded.push(node),
+		}
+	}
+	Ok(expanded)
+}
+
+fn instantiate(
+	usage: &HtmlElement,
+	template: &HtmlTemplate,
+	templates: &HashMap<String, HtmlTemplate>,
+	depth: usize,
+) -> Result<Vec<HtmlNode>, HtmlUiError> {
+	let params: HashMap<&str, &str> = template
+		.params
+		.iter()
+		.map(|param| (param.as_str(), usage.attribute(param).unwrap_or_default()))
+		.collect();
+
+	let slot = expand_nodes(usage.children.clone(), templates, depth)?;
+	let mut body = substitute_nodes(&template.body, &params, &slot);
+
+	// The attributes on the tag itself, other than the params, carry over to a template with a
+	// single root element, over those of the root.
+	let attributes = usage
+		.attributes
+		.iter()
+		.filter(|(name, _)| !template.params.contains(name));
+	if let [HtmlNode::Element(root)] = body.as_mut_slice() {
+		if usage.name_id.is_some() {
+			root.name_id.clone_from(&usage.name_id);
+		}
+		root.classes.extend(usage.classes.iter().cloned());
+		if usage.gap != Val::Auto {
+			root.gap = usage.gap;
+		}
+		root.autofocus |= usage.autofocus;
+		if usage.callback.is_some() {
+			root.callback.clone_from(&usage.callback);
+		}
+		if usage.each.is_some() {
+			root.each.clone_from(&usage.each);
+		}
+		for (name, value) in attributes {
+			match root
+				.attributes
+				.iter_mut()
+				.find(|(root_name, _)| root_name == name)
+			{
+				Some((_, root_value)) => root_value.clone_from(value),
+				None => root.attributes.push((name.clone(), value.clone())),
+			}
+		}
+	} else if let Some(attribute) = usage
+		.name_id
+		.as_ref()
+		.map(|_| "id")
+		.or_else(|| (!usage.classes.is_empty()).then_some("class"))
+		.or_else(|| (usage.gap != Val::Auto).then_some("gap"))
+		.or_else(|| usage.autofocus.then_some("autofocus"))
+		.or_else(|| usage.callback.as_ref().map(|_| "callback"))
+		.or_else(|| usage.each.as_ref().map(|_| "for"))
+		.or_else(|| attributes.map(|(name, _)| name.as_str()).next())
+	{
+		return Err(HtmlUiError::ParseError(format!(
+			"`{attribute}` on <{}> needs the template to have a single root element",
+			usage.tag.as_str()
+		)));
+	}
+
+	expand_nodes(body, templates, depth + 1)
+}
+
+fn substitute_nodes(
+	nodes: &[HtmlNode],
+	params: &HashMap<&str, &str>,
+	slot: &[HtmlNode],
+) -> Vec<HtmlNode> {
+	let mut substituted = Vec::with_capacity(nodes.len());
+	for node in nodes {
+		match node {
+			HtmlNode::Text(text) => substituted.push(HtmlNode::Text(substitute(text, params))),
+			HtmlNode::Element(element) if element.tag == HtmlTag::Slot => {
+				substituted.extend(slot.iter().cloned());
+			}
+			HtmlNode::Element(element) => {
+				substituted.push(HtmlNode::Element(HtmlElement {
+					name_id: element.name_id.as_deref().map(|id| substitute(id, params)),
+					classes: element
+						.classes
+						.iter()
+						.flat_map(|class| {
+							substitute(class, params)
+								.split_whitespace()
+								.map(str::to_owned)
+								.collect::<Vec<_>>()
+						})
+						.collect(),
+					callback: element
+						.callback
+						.as_deref()
+						.map(|callback| substitute(callback, params)),
+					each: element.each.as_ref().map(|each| HtmlEach {
+						binding: each.binding.clone(),
+						collection: substitute(&each.collection, params),
+						key: each.key.as_deref().map(|key| substitute(key, params)),
+					}),
+					attributes: element
+						.attributes
+						.iter()
+						.map(|(name, value)| (name.clone(), substitute(value, params)))
+						.collect(),
+					children: substitute_nodes(&element.children, params, slot),
+					..element.clone()
+				}));
+			}
+		}
+	}
+	substituted
+}
+
+/// Replaces `{{ param }}` for the template's own params only, leaving any other `{{ path }}`
+/// for data binding.
+fn substitute(text: &str, params: &HashMap<&str, &str>) -> String {
+	let mut out = String::with_capacity(text.len());
+	let mut rest = text;
+
+	while let Some(start) = rest.find("{{") {
+		let after = &rest[start + 2..];
+		let Some(end) = after.find("}}") else {
+			break;
+		};
+		out.push_str(&rest[..start]);
+		match params.get(after[..end].trim()) {
+			Some(value) => out.push_str(value),
+			None => out.push_str(&rest[start..start + 2 + end + 2]),
+		}
+		rest = &after[end + 2..];
+	}
+
+	out.push_str(rest);
+	out
+}
+
+#[cfg(test)]
+mod template_tests {
+	use bevy::ui::Val;
+
+	use crate::{
+		ast::{HtmlEach, HtmlElement, HtmlNode, HtmlTag},
+		parser::parse_htmlish,
+	};
+
+	use super::expand_templates;
+
+	const TEMPLATE_HTML: &str = r#"
+		<template name="menu-button" params="label action">
+			<button class="button" callback="{{ action }}">
+				<vbox>
+					<label>{{ label }} {{ player.name }}</label>
+					<slot />
+				</vbox>
+			</button>
+		</template>
+		<ui>
+			<menu-button label="Quit" action="quit" class="quit"><spacer /></menu-button>
+		</ui>
+		"#;
+
+	#[test]
+	fn test_expand() {
+		let parsed = parse_htmlish(&TEMPLATE_HTML.into()).expect("templates failed to parse");
+		let (expanded, templates) = expand_templates(parsed).expect("templates failed to expand");
+		assert_eq!(expanded.len(), 1, "<template> was not removed.");
+		assert_eq!(templates.len(), 1, "<template> was not kept.");
+		let HtmlNode::Element(HtmlElement { children, .. }) = &expanded[0] else {
+			panic!("<ui> is not Element");
+		};
+		let HtmlNode::Element(HtmlElement {
+			tag,
+			classes,
+			callback,
+			children,
+			..
+		}) = &children[0]
+		else {
+			panic!("<menu-button> is not Element");
+		};
+		assert_eq!(*tag, HtmlTag::Named("button".into()));
+		assert_eq!(*classes, vec!["button".to_owned(), "quit".to_owned()]);
+		assert_eq!(*callback, Some("quit".to_owned()));
+		let HtmlNode::Element(HtmlElement { children, .. }) = &children[0] else {
+			panic!("<vbox> is not Element");
+		};
+		assert_eq!(children.len(), 2, "<slot> was not filled.");
+		let HtmlNode::Element(HtmlElement {
+			children: label, ..
+		}) = &children[0]
+		else {
+			panic!("<label> is not Element");
+		};
+		let HtmlNode::Text(text) = &label[0] else {
+			panic!("<label> text is not Text");
+		};
+		assert_eq!(text, "Quit {{ player.name }}");
+		let HtmlNode::Element(HtmlElement { tag, .. }) = &children[1] else {
+			panic!("<spacer> is not Element");
+		};
+		assert_eq!(*tag, HtmlTag::Named("spacer".into()));
+
+		let list = parse_htmlish(
+			&r#"<template name="item-list" params="items field"><label for="item in {{ items }}" key="item.{{ field }}">{{ item.name }}</label></template><item-list items="inventory.items" field="slot" />"#
+				.into(),
+		)
+		.unwrap();
+		let (expanded, _) = expand_templates(list).expect("templates failed to expand");
+		let HtmlNode::Element(HtmlElement { each, .. }) = &expanded[0] else {
+			panic!("<item-list> is not Element");
+		};
+		assert_eq!(
+			*each,
+			Some(HtmlEach {
+				binding: "item".to_owned(),
+				collection: "inventory.items".to_owned(),
+				key: Some("item.slot".to_owned()),
+			})
+		);
+
+		let recursive =
+			parse_htmlish(&r#"<template name="loop"><loop /></template><ui><loop /></ui>"#.into())
+				.unwrap();
+		assert!(
+			expand_templates(recursive).is_err(),
+			"Recursive template was accepted."
+		);
+	}
+
+	/// Attributes on the tag other than its params are applied to the root, over its own.
+	#[test]
+	fn test_usage_attributes() {
+		let parsed = parse_htmlish(
+			&r#"<template name="card" params="label"><vbox gap="2px" width="10px" class="card"><label>{{ label }}</label></vbox></template><card label="Hi" gap="4px" width="20px" dir="rtl" t="title" style="color: red;" stylesheet="card.css" />"#
+				.into(),
+		)
+		.unwrap();
+		let (expanded, _) = expand_templates(parsed).expect("templates failed to expand");
+		let HtmlNode::Element(root) = &expanded[0] else {
+			panic!("<card> is not Element");
+		};
+		assert_eq!(root.tag, HtmlTag::Named("vbox".into()));
+		assert_eq!(root.gap, Val::Px(4.0));
+		assert_eq!(root.attribute("width"), Some("20px"));
+		assert_eq!(root.attribute("dir"), Some("rtl"));
+		assert_eq!(root.attribute("t"), Some("title"));
+		assert_eq!(root.attribute("style"), Some("color: red;"));
+		assert_eq!(root.attribute("stylesheet"), Some("card.css"));
+		assert_eq!(root.attribute("label"), None);
+
+		let several = parse_htmlish(
+			&r#"<template name="pair"><label>A</label><label>B</label></template><pair width="20px" />"#
+				.into(),
+		)
+		.unwrap();
+		assert!(
+			expand_templates(several).is_err(),
+			"Attribute on a template without a single root was dropped."
+		);
+	}
+
+	/// A name can only be given to one template, though the same one may be defined again.
+	#[test]
+	fn test_duplicate_templates() {
+		let twice = parse_htmlish(
+			&r#"<template name="card"><label>A</label></template><template name="card"><label>B</label></template><card />"#
+				.into(),
+		)
+		.unwrap();
+		assert!(
+			expand_templates(twice).is_err(),
+			"Second template with the same name was accepted."
+		);
+
+		let same = parse_htmlish(
+			&r#"<template name="card"><label>A</label></template><template name="card"><label>A</label></template><card />"#
+				.into(),
+		)
+		.unwrap();
+		assert!(expand_templates(same).is_ok());
+	}
+}
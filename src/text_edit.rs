@@ -0,0 +1,363 @@
+use std::ops::Range;
+
+use bevy::{prelude::*, text::ComputedTextBlock};
+
+/// The text, caret and selection of an editable text field, with byte indices on char boundaries.
+///
+/// The selection runs between `anchor` and `cursor`, and is empty when they are equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TextEdit {
+	pub value: String,
+	pub cursor: usize,
+	pub anchor: usize,
+}
+
+impl TextEdit {
+	pub fn new(value: impl Into<String>) -> Self {
+		let value = value.into();
+		let end = value.len();
+		Self {
+			value,
+			cursor: end,
+			anchor: end,
+		}
+	}
+
+	pub fn selection(&self) -> Range<usize> {
+		self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+	}
+
+	pub fn selected_text(&self) -> &str {
+		&self.value[self.selection()]
+	}
+
+	/// Replaces the whole text and puts the caret at its end.
+	pub fn set_value(&mut self, value: impl Into<String>) {
+		*self = Self::new(value);
+	}
+
+	pub fn select_all(&mut self) {
+		self.anchor = 0;
+		self.cursor = self.value.len();
+	}
+
+	/// Moves the caret to `index`, extending the selection if `select` is set.
+	pub fn move_to(&mut self, index: usize, select: bool) {
+		self.cursor = floor_char_boundary(&self.value, index);
+		if !select {
+			self.anchor = self.cursor;
+		}
+	}
+
+	/// Moves the caret one char (or word) left, or to the start of the selection.
+	pub fn move_left(&mut self, word: bool, select: bool) {
+		let selection = self.selection();
+		if !select && !selection.is_empty() {
+			self.move_to(selection.start, false);
+		} else {
+			self.move_to(self.prev_boundary(self.cursor, word), select);
+		}
+	}
+
+	/// Moves the caret one char (or word) right, or to the end of the selection.
+	pub fn move_right(&mut self, word: bool, select: bool) {
+		let selection = self.selection();
+		if !select && !selection.is_empty() {
+			self.move_to(selection.end, false);
+		} else {
+			self.move_to(self.next_boundary(self.cursor, word), select);
+		}
+	}
+
+	pub fn line_start(&self, index: usize) -> usize {
+		self.value[..index]
+			.rfind('\n')
+			.map_or(0, |newline| newline + 1)
+	}
+
+	pub fn line_end(&self, index: usize) -> usize {
+		self.value[index..]
+			.find('\n')
+			.map_or(self.value.len(), |newline| index + newline)
+	}
+
+	/// Replaces the selection with `text`, cut short so the value stays within `max_chars`.
+	///
+	/// Returns whether the value changed.
+	pub fn insert(&mut self, text: &str, max_chars: Option<usize>) -> bool {
+		let selection = self.selection();
+		let mut text = text;
+		if let Some(max_chars) = max_chars {
+			let kept = self.value.chars().count() - self.value[selection.clone()].chars().count();
+			let room = max_chars.saturating_sub(kept);
+			if let Some((end, _)) = text.char_indices().nth(room) {
+				text = &text[..end];
+			}
+		}
+		if selection.is_empty() && text.is_empty() {
+			return false;
+		}
+		self.value.replace_range(selection.clone(), text);
+		self.move_to(selection.start + text.len(), false);
+		true
+	}
+
+	/// Deletes the selection, or the char (or word) before the caret.
+	pub fn delete_backward(&mut self, word: bool) -> bool {
+		if self.selection().is_empty() {
+			self.anchor = self.prev_boundary(self.cursor, word);
+		}
+		self.insert("", None)
+	}
+
+	/// Deletes the selection, or the char (or word) after the caret.
+	pub fn delete_forward(&mut self, word: bool) -> bool {
+		if self.selection().is_empty() {
+			self.anchor = self.next_boundary(self.cursor, word);
+		}
+		self.insert("", None)
+	}
+
+	fn prev_boundary(&self, index: usize, word: bool) -> usize {
+		let mut chars = self.value[..index].char_indices().rev().peekable();
+		if !word {
+			return chars.next().map_or(0, |(i, _)| i);
+		}
+		while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+		let mut boundary = chars.peek().map_or(0, |(i, _)| *i);
+		while let Some((i, _)) = chars.next_if(|(_, c)| !c.is_whitespace()) {
+			boundary = i;
+		}
+		boundary
+	}
+
+	fn next_boundary(&self, index: usize, word: bool) -> usize {
+		let mut chars = self.value[index..].char_indices().peekable();
+		if word {
+			while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+			while chars.next_if(|(_, c)| !c.is_whitespace()).is_some() {}
+		} else {
+			chars.next();
+		}
+		chars.peek().map_or(self.value.len(), |(i, _)| index + i)
+	}
+}
+
+pub(crate) fn floor_char_boundary(text: &str, index: usize) -> usize {
+	let mut index = index.min(text.len());
+	while !text.is_char_boundary(index) {
+		index -= 1;
+	}
+	index
+}
+
+/// The line of `text` that `index` is on, and the byte offset within that line.
+fn line_and_column(text: &str, index: usize) -> (usize, usize) {
+	let before = &text[..index];
+	let line = before.matches('\n').count();
+	let column = index - before.rfind('\n').map_or(0, |newline| newline + 1);
+	(line, column)
+}
+
+fn line_offset(text: &str, line: usize) -> usize {
+	if line == 0 {
+		return 0;
+	}
+	text.match_indices('\n')
+		.nth(line - 1)
+		.map_or(text.len(), |(newline, _)| newline + 1)
+}
+
+/// The caret before byte `index` of `text` as a zero-width rect spanning its line, in the physical
+/// pixels of the laid out `block`. Returns `None` until `block` has been laid out.
+pub(crate) fn caret_rect(block: &ComputedTextBlock, text: &str, index: usize) -> Option<Rect> {
+	let (line, column) = line_and_column(text, floor_char_boundary(text, index));
+	let mut end_of_line = None;
+	let mut last_bottom = None;
+	for run in block.buffer().0.layout_runs() {
+		let top = run.line_top;
+		let bottom = run.line_top + run.line_height;
+		last_bottom = Some((bottom, run.line_height));
+		if run.line_i != line {
+			continue;
+		}
+		if let Some(glyph) = run
+			.glyphs
+			.iter()
+			.find(|glyph| glyph.start <= column && column < glyph.end)
+		{
+			return Some(Rect::new(glyph.x, top, glyph.x, bottom));
+		}
+		let end = run.glyphs.last().map_or(0.0, |glyph| glyph.x + glyph.w);
+		end_of_line = Some(Rect::new(end, top, end, bottom));
+	}
+	// A line that has not been laid out yet, such as one just started with Enter, goes below the rest.
+	end_of_line.or_else(|| {
+		let (top, height) = last_bottom.filter(|_| line > 0)?;
+		Some(Rect::new(0.0, top, 0.0, top + height))
+	})
+}
+
+/// The byte index of `text` on the laid out line above or below the caret at `index`, keeping
+/// the caret's horizontal position. Returns `None` past the first or last line.
+pub(crate) fn line_above_or_below(
+	block: &ComputedTextBlock,
+	text: &str,
+	index: usize,
+	below: bool,
+) -> Option<usize> {
+	let caret = caret_rect(block, text, index)?;
+	let y = if below {
+		caret.max.y + caret.height() / 2.0
+	} else {
+		caret.min.y - caret.height() / 2.0
+	};
+	if y < 0.0 {
+		return None;
+	}
+	let cursor = block.buffer().0.hit(caret.min.x, y)?;
+	let found = floor_char_boundary(text, line_offset(text, cursor.line) + cursor.index);
+	let moved = caret_rect(block, text, found)?;
+	((moved.min.y - caret.min.y).abs() > f32::EPSILON).then_some(found)
+}
+
+/// One rect per laid out line covering the glyphs of `text` within `range`, in physical pixels.
+pub(crate) fn selection_rects(
+	block: &ComputedTextBlock,
+	text: &str,
+	range: &Range<usize>,
+) -> Vec<Rect> {
+	let mut rects = Vec::new();
+	if range.is_empty() {
+		return rects;
+	}
+	for run in block.buffer().0.layout_runs() {
+		let offset = line_offset(text, run.line_i);
+		let mut rect: Option<Rect> = None;
+		for glyph in run.glyphs {
+			if !range.contains(&(offset + glyph.start)) {
+				continue;
+			}
+			let glyph_rect = Rect::new(
+				glyph.x,
+				run.line_top,
+				glyph.x + glyph.w,
+				run.line_top + run.line_height,
+			);
+			rect = Some(rect.map_or(glyph_rect, |rect| rect.union(glyph_rect)));
+		}
+		rects.extend(rect);
+	}
+	rects
+}
+
+/// The byte index of `text` closest to `point`, in the physical pixels of the laid out `block`.
+pub(crate) fn hit_index(block: &ComputedTextBlock, text: &str, point: Vec2) -> Option<usize> {
+	let cursor = block.buffer().0.hit(point.x, point.y)?;
+	let index = line_offset(text, cursor.line) + cursor.index;
+	Some(floor_char_boundary(text, index))
+}
+
+#[cfg(test)]
+mod text_edit_tests {
+	use bevy::{
+		prelude::*,
+		text::{
+			ComputedTextBlock, CosmicFontSystem, DEFAULT_FONT_DATA, FontHinting, LineHeight,
+			TextBounds, TextPipeline,
+		},
+	};
+
+	use super::{TextEdit, caret_rect, line_above_or_below};
+
+	/// Lays out `text` on unbounded lines in the monospace default font.
+	fn laid_out(text: &str) -> ComputedTextBlock {
+		let mut fonts = Assets::<Font>::default();
+		fonts
+			.insert(
+				AssetId::default(),
+				Font::try_from_bytes(DEFAULT_FONT_DATA.to_vec()).unwrap(),
+			)
+			.unwrap();
+		let mut block = ComputedTextBlock::default();
+		TextPipeline::default()
+			.update_buffer(
+				&fonts,
+				[(
+					Entity::PLACEHOLDER,
+					0,
+					text,
+					&TextFont::default(),
+					Color::WHITE,
+					LineHeight::default(),
+				)]
+				.into_iter(),
+				LineBreak::NoWrap,
+				Justify::Left,
+				TextBounds::UNBOUNDED,
+				1.0,
+				&mut block,
+				&mut CosmicFontSystem::default(),
+				FontHinting::default(),
+			)
+			.unwrap();
+		block
+	}
+
+	#[test]
+	fn test_editing() {
+		let mut edit = TextEdit::new("hello wörld");
+		edit.move_left(true, false);
+		assert_eq!(edit.cursor, 6);
+		edit.move_right(false, true);
+		edit.move_right(false, true);
+		assert_eq!(edit.selected_text(), "wö");
+		assert!(edit.insert("W", None));
+		assert_eq!(edit.value, "hello Wrld");
+		assert!(edit.delete_backward(true));
+		assert_eq!(edit.value, "hello rld");
+
+		edit.select_all();
+		assert!(edit.insert("abcdef", Some(4)));
+		assert_eq!(edit.value, "abcd");
+		assert!(!edit.insert("e", Some(4)), "maxlength was exceeded");
+		edit.move_to(0, false);
+		assert!(edit.delete_forward(false));
+		assert_eq!(edit.value, "bcd");
+
+		let edit = TextEdit::new("one\ntwo");
+		assert_eq!(edit.line_start(5), 4);
+		assert_eq!(edit.line_end(1), 3);
+	}
+
+	#[test]
+	fn test_line_above_or_below() {
+		let text = "abcdef\nab\nabcd";
+		let block = laid_out(text);
+
+		// Moving onto a shorter line puts the caret at its end, and onto a longer one keeps the
+		// column.
+		assert_eq!(line_above_or_below(&block, text, 5, true), Some(9));
+		assert_eq!(line_above_or_below(&block, text, 9, true), Some(12));
+		assert_eq!(line_above_or_below(&block, text, 14, false), Some(9));
+		assert_eq!(line_above_or_below(&block, text, 8, false), Some(1));
+		assert_eq!(line_above_or_below(&block, text, 2, false), None);
+		assert_eq!(line_above_or_below(&block, text, 12, true), None);
+	}
+
+	#[test]
+	fn test_caret_rect() {
+		let text = "abcdef\nab";
+		let block = laid_out(text);
+		let caret = |index| caret_rect(&block, text, index).unwrap();
+		let advance = caret(1).min.x - caret(0).min.x;
+		assert!(advance > 0.0);
+
+		// At the end of a line the caret is after its last glyph, on that line.
+		assert!((caret(6).min.x - 6.0 * advance).abs() < 0.5);
+		assert_eq!(caret(6).min.y, caret(0).min.y);
+		assert!((caret(9).min.x - 2.0 * advance).abs() < 0.5);
+		assert!((caret(9).min.y - caret(0).max.y).abs() < 0.5);
+		assert_eq!(caret(9).width(), 0.0);
+	}
+}
@@ -0,0 +1,405 @@
+use std::{
+	collections::HashMap,
+	fmt::{self, Write as _},
+	sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy::{
+	asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+	log::{warn, warn_once},
+	prelude::*,
+	reflect::TypePath,
+};
+use fluent_bundle::{
+	FluentArgs, FluentError, FluentResource, FluentValue,
+	concurrent::FluentBundle,
+	resolver::{ResolverError, errors::ReferenceKind},
+};
+use unic_langid::LanguageIdentifier;
+
+use crate::error::HtmlUiError;
+
+/// The messages of one language, loaded from a Fluent `.ftl` file or a `.lang.ron` map of keys to
+/// messages, or built in code with [`with`](Self::with).
+///
+/// Other formats can be supported by registering an [`AssetLoader`] for this asset.
+#[derive(Asset, TypePath, Debug, Default, Clone)]
+pub struct HtmlTranslations {
+	messages: HashMap<String, Pattern>,
+	fluent: Option<FluentMessages>,
+}
+
+/// A message of the key-value syntax, such as `Hello, {name}!`.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Pattern(Vec<Piece>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+	Text(String),
+	/// `{name}`.
+	Argument(String),
+}
+
+/// The messages of a Fluent file, with a bundle for each language they were formatted in, as the
+/// language picks the plural rules of selectors.
+#[derive(Clone)]
+struct FluentMessages {
+	resource: Arc<FluentResource>,
+	bundles: Arc<Mutex<HashMap<String, FluentBundle<Arc<FluentResource>>>>>,
+}
+
+impl fmt::Debug for FluentMessages {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("FluentMessages").finish_non_exhaustive()
+	}
+}
+
+impl FluentMessages {
+	/// Calls `f` with the bundle of `language`, a tag such as `pt-BR`.
+	fn with_bundle<T>(
+		&self,
+		language: &str,
+		f: impl FnOnce(&FluentBundle<Arc<FluentResource>>) -> T,
+	) -> T {
+		let mut bundles = self.bundles.lock().unwrap_or_else(PoisonError::into_inner);
+		let bundle = bundles.entry(language.to_owned()).or_insert_with(|| {
+			let id = language.parse::<LanguageIdentifier>().unwrap_or_else(|_| {
+				warn_once!("invalid language `{language}`, so selectors use their default variant");
+				LanguageIdentifier::default()
+			});
+			let mut bundle = FluentBundle::new_concurrent(vec![id]);
+			// Bevy draws the marks isolating arguments from the text around them.
+			bundle.set_use_isolating(false);
+			if let Err(errors) = bundle.add_builtins() {
+				warn!("failed to add the Fluent functions: {errors:?}");
+			}
+			if let Err(errors) = bundle.add_resource(self.resource.clone()) {
+				for error in errors {
+					warn!("{error}");
+				}
+			}
+			bundle
+		});
+		f(bundle)
+	}
+}
+
+impl HtmlTranslations {
+	/// Adds a message in the key-value syntax, where `{name}` is replaced by the argument `name`.
+	#[must_use]
+	pub fn with(mut self, key: impl Into<String>, message: &str) -> Self {
+		self.insert(key, message);
+		self
+	}
+
+	/// Adds a message in the key-value syntax, where `{name}` is replaced by the argument `name`.
+	pub fn insert(&mut self, key: impl Into<String>, message: &str) {
+		self.messages.insert(key.into(), parse_braced(message));
+	}
+
+	#[must_use]
+	pub fn contains(&self, key: &str) -> bool {
+		self.messages.contains_key(key)
+			|| self.fluent.as_ref().is_some_and(|fluent| {
+				let (id, attribute) = fluent_key(key);
+				fluent.with_bundle("und", |bundle| {
+					bundle
+						.get_message(id)
+						.is_some_and(|message| match attribute {
+							Some(attribute) => message.get_attribute(attribute).is_some(),
+							None => message.value().is_some(),
+						})
+				})
+			})
+	}
+
+	/// Formats the message `key` with `args`, or returns `None` if there is no such message.
+	/// Arguments missing from `args` are written as `{$name}`, and numbers select plural variants
+	/// such as `[few]` by the rules of `language`.
+	#[must_use]
+	pub fn format(&self, language: &str, key: &str, args: &[(String, String)]) -> Option<String> {
+		if let Some(pattern) = self.messages.get(key) {
+			let mut out = String::new();
+			for piece in &pattern.0 {
+				match piece {
+					Piece::Text(text) => out.push_str(text),
+					Piece::Argument(name) => match args.iter().find(|(arg, _)| arg == name) {
+						Some((_, value)) => out.push_str(value),
+						None => _ = write!(out, "{{${name}}}"),
+					},
+				}
+			}
+			return Some(out);
+		}
+
+		self.fluent.as_ref()?.with_bundle(language, |bundle| {
+			let (id, attribute) = fluent_key(key);
+			let message = bundle.get_message(id)?;
+			let pattern = match attribute {
+				Some(attribute) => message.get_attribute(attribute)?.value(),
+				None => message.value()?,
+			};
+			let args: FluentArgs = args
+				.iter()
+				.map(|(name, value)| (name.as_str(), FluentValue::try_number(value)))
+				.collect();
+			let mut errors = Vec::new();
+			let out = bundle.format_pattern(pattern, Some(&args), &mut errors);
+			for error in errors {
+				// Like in the key-value syntax, missing arguments are left to show as `{$name}`.
+				if !matches!(
+					error,
+					FluentError::ResolverError(ResolverError::Reference(
+						ReferenceKind::Variable { .. }
+					))
+				) {
+					warn!("message `{key}`: {error}");
+				}
+			}
+			Some(out.into_owned())
+		})
+	}
+}
+
+/// The Fluent message and attribute named by `key`, such as `login.placeholder` for the
+/// `placeholder` of `login`.
+fn fluent_key(key: &str) -> (&str, Option<&str>) {
+	match key.split_once('.') {
+		Some((id, attribute)) => (id, Some(attribute)),
+		None => (key, None),
+	}
+}
+
+/// Parses a message of the key-value syntax, such as `Hello, {name}!`.
+fn parse_braced(message: &str) -> Pattern {
+	let mut pieces = Vec::new();
+	let mut rest = message;
+	while let Some(start) = rest.find('{') {
+		let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+			break;
+		};
+		if start > 0 {
+			pieces.push(Piece::Text(rest[..start].to_owned()));
+		}
+		pieces.push(Piece::Argument(rest[start + 1..end].trim().to_owned()));
+		rest = &rest[end + 1..];
+	}
+	if !rest.is_empty() {
+		pieces.push(Piece::Text(rest.to_owned()));
+	}
+	Pattern(pieces)
+}
+
+/// Parses a Fluent file, failing with the line of each syntax error.
+pub(crate) fn parse_ftl(source: &str) -> Result<HtmlTranslations, HtmlUiError> {
+	let resource = FluentResource::try_new(source.to_owned()).map_err(|(_, errors)| {
+		let errors: Vec<String> = errors
+			.iter()
+			.map(|error| {
+				let line = source[..error.pos.start.min(source.len())]
+					.matches('\n')
+					.count() + 1;
+				format!("line {line}: {error}")
+			})
+			.collect();
+		HtmlUiError::ParseError(errors.join(", "))
+	})?;
+	Ok(HtmlTranslations {
+		messages: HashMap::new(),
+		fluent: Some(FluentMessages {
+			resource: Arc::new(resource),
+			bundles: Arc::default(),
+		}),
+	})
+}
+
+/// Loads Fluent `.ftl` files.
+#[derive(TypePath)]
+pub struct HtmlFluentLoader;
+
+impl AssetLoader for HtmlFluentLoader {
+	type Asset = HtmlTranslations;
+	type Settings = ();
+	type Error = HtmlUiError;
+
+	async fn load(
+		&self,
+		reader: &mut dyn Reader,
+		_settings: &Self::Settings,
+		_load_context: &mut LoadContext<'_>,
+	) -> Result<Self::Asset, Self::Error> {
+		let mut source = String::new();
+		reader
+			.read_to_string(&mut source)
+			.await
+			.map_err(HtmlUiError::IoError)?;
+		parse_ftl(&source)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["ftl"]
+	}
+}
+
+/// Loads `.lang.ron` files holding a map of keys to messages in the key-value syntax, such as
+/// `{ "menu.continue": "Continue", "greeting": "Hello, {name}!" }`.
+#[derive(TypePath)]
+pub struct HtmlKeyValueLoader;
+
+impl AssetLoader for HtmlKeyValueLoader {
+	type Asset = HtmlTranslations;
+	type Settings = ();
+	type Error = HtmlUiError;
+
+	async fn load(
+		&self,
+		reader: &mut dyn Reader,
+		_settings: &Self::Settings,
+		_load_context: &mut LoadContext<'_>,
+	) -> Result<Self::Asset, Self::Error> {
+		let mut source = String::new();
+		reader
+			.read_to_string(&mut source)
+			.await
+			.map_err(HtmlUiError::IoError)?;
+		let messages: HashMap<String, String> =
+			ron::de::from_str(&source).map_err(|err| HtmlUiError::ParseError(err.to_string()))?;
+		let mut translations = HtmlTranslations::default();
+		for (key, message) in messages {
+			translations.insert(key, &message);
+		}
+		Ok(translations)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["lang.ron"]
+	}
+}
+
+#[cfg(test)]
+mod translations_tests {
+	use super::{HtmlTranslations, parse_ftl};
+
+	fn args(args: &[(&str, &str)]) -> Vec<(String, String)> {
+		args.iter()
+			.map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+			.collect()
+	}
+
+	#[test]
+	fn test_fluent() {
+		let translations = parse_ftl(
+			"# Main menu\n\
+			-brand = Flair\n\
+			menu-continue = Continue\n\
+			welcome = Welcome to { -brand }, { $name }!\n\
+			items = { $count ->\n    [0] No items\n    [one] One item\n   *[other] { $count } items\n}\n\
+			login =\n    .placeholder = Your { \"name\" }\n\
+			about =\n    First line\n    second line\n",
+		)
+		.expect("ftl failed to parse");
+
+		assert_eq!(
+			translations.format("en", "menu-continue", &[]).as_deref(),
+			Some("Continue")
+		);
+		assert_eq!(
+			translations
+				.format("en", "welcome", &args(&[("name", "Amy")]))
+				.as_deref(),
+			Some("Welcome to Flair, Amy!")
+		);
+		for (count, expected) in [("0", "No items"), ("1", "One item"), ("3", "3 items")] {
+			assert_eq!(
+				translations
+					.format("en", "items", &args(&[("count", count)]))
+					.as_deref(),
+				Some(expected)
+			);
+		}
+		assert_eq!(
+			translations
+				.format("en", "login.placeholder", &[])
+				.as_deref(),
+			Some("Your name")
+		);
+		assert!(!translations.contains("login"));
+		assert_eq!(
+			translations.format("en", "about", &[]).as_deref(),
+			Some("First line\nsecond line")
+		);
+		assert_eq!(
+			translations.format("en", "welcome", &[]).as_deref(),
+			Some("Welcome to Flair, {$name}!")
+		);
+		assert!(translations.format("en", "missing", &[]).is_none());
+
+		assert!(parse_ftl("no value").is_err());
+		assert!(parse_ftl("broken = { $count ->\n    [one] One\n}\n").is_err());
+	}
+
+	/// String literals may hold braces, quotes and escapes, and functions and terms take arguments.
+	#[test]
+	fn test_fluent_syntax() {
+		let translations = parse_ftl(
+			"braces = Open { \"{\" } and { \"say \\\"hi\\\"\" } { \"\\u0041\" }\n\
+			price = { NUMBER($amount, minimumFractionDigits: 2) }\n\
+			-brand = { $case ->\n    [upper] FLAIR\n   *[other] Flair\n}\n\
+			title = { -brand(case: \"upper\") }\n",
+		)
+		.expect("ftl failed to parse");
+		assert_eq!(
+			translations.format("en", "braces", &[]).as_deref(),
+			Some("Open { and say \"hi\" A")
+		);
+		assert_eq!(
+			translations
+				.format("en", "price", &args(&[("amount", "3")]))
+				.as_deref(),
+			Some("3.00")
+		);
+		assert_eq!(
+			translations.format("en", "title", &[]).as_deref(),
+			Some("FLAIR")
+		);
+
+		let Err(err) = parse_ftl("ok = Fine\ndouble = { $n ->\n   **[other] Many\n}\n") else {
+			panic!("two default markers parsed");
+		};
+		assert!(err.to_string().contains("line 3"), "{err}");
+	}
+
+	#[test]
+	fn test_plurals() {
+		let translations = parse_ftl(
+			"apples = { $count ->\n    [one] { $count } jabłko\n    [few] { $count } jabłka\n   *[many] { $count } jabłek\n}\n",
+		)
+		.expect("ftl failed to parse");
+		for (language, count, expected) in [
+			("pl", "1", "1 jabłko"),
+			("pl", "3", "3 jabłka"),
+			("pl", "5", "5 jabłek"),
+			("pl", "22", "22 jabłka"),
+			("ja", "1", "1 jabłek"),
+		] {
+			assert_eq!(
+				translations
+					.format(language, "apples", &args(&[("count", count)]))
+					.as_deref(),
+				Some(expected),
+				"{language} {count}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_key_value() {
+		let translations = HtmlTranslations::default().with("greeting", "Hello, {name}!");
+		assert_eq!(
+			translations
+				.format("en", "greeting", &args(&[("name", "Amy")]))
+				.as_deref(),
+			Some("Hello, Amy!")
+		);
+	}
+}
@@ -0,0 +1,40 @@
+__html_ui::HtmlUiAsset::compiled(|world: &mut __html_ui::World, e0: __html_ui::Entity| {
+{
+let element = __html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("ui")), name_id: ::std::option::Option::None, classes: ::std::vec![::std::string::String::from("menu"),::std::string::String::from("main"),], gap: __html_ui::Val::Percent(2.0), autofocus: false, callback: ::std::option::Option::None, each: ::std::option::Option::None, attributes: ::std::vec![], children: ::std::vec![] };
+let (e1, children) = __html_ui::begin_element(world, e0, &element);
+if children.elements() {
+{
+let element = __html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("vbox")), name_id: ::std::option::Option::Some(::std::string::String::from("buttons")), classes: ::std::vec![], gap: __html_ui::Val::Px(4.0), autofocus: false, callback: ::std::option::Option::None, each: ::std::option::Option::None, attributes: ::std::vec![], children: ::std::vec![] };
+let (e2, children) = __html_ui::begin_element(world, e1, &element);
+if children.elements() {
+{
+let element = __html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("label")), name_id: ::std::option::Option::None, classes: ::std::vec![], gap: __html_ui::Val::Auto, autofocus: false, callback: ::std::option::Option::None, each: ::std::option::Option::None, attributes: ::std::vec![], children: ::std::vec![__html_ui::HtmlNode::Text(::std::string::String::from("Say \"hi\" to {{ player.name }}")),] };
+let (e3, children) = __html_ui::begin_element(world, e2, &element);
+if children.text() { __html_ui::spawn_text(world, e3, "Say \"hi\" to {{ player.name }}"); }
+__html_ui::end_element(world, e3, &element);
+}
+}
+if children.elements() {
+{
+let element = __html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("button")), name_id: ::std::option::Option::None, classes: ::std::vec![], gap: __html_ui::Val::Auto, autofocus: true, callback: ::std::option::Option::Some(::std::string::String::from("play")), each: ::std::option::Option::None, attributes: ::std::vec![], children: ::std::vec![__html_ui::HtmlNode::Text(::std::string::String::from("Play")),] };
+let (e3, children) = __html_ui::begin_element(world, e2, &element);
+if children.text() { __html_ui::spawn_text(world, e3, "Play"); }
+__html_ui::end_element(world, e3, &element);
+}
+}
+if children.elements() {
+__html_ui::spawn_node(world, e2, &__html_ui::HtmlNode::Element(__html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("hbox")), name_id: ::std::option::Option::None, classes: ::std::vec![::std::string::String::from("slot"),], gap: __html_ui::Val::Auto, autofocus: false, callback: ::std::option::Option::None, each: ::std::option::Option::Some(__html_ui::HtmlEach { binding: ::std::string::String::from("item"), collection: ::std::string::String::from("inventory.items"), key: ::std::option::Option::Some(::std::string::String::from("item.id")) }), attributes: ::std::vec![], children: ::std::vec![__html_ui::HtmlNode::Element(__html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("progress")), name_id: ::std::option::Option::None, classes: ::std::vec![], gap: __html_ui::Val::Auto, autofocus: false, callback: ::std::option::Option::None, each: ::std::option::Option::None, attributes: ::std::vec![(::std::string::String::from("value"), ::std::string::String::from("{{ item.durability }}")),(::std::string::String::from("max"), ::std::string::String::from("100")),], children: ::std::vec![] }),] }));
+}
+if children.elements() {
+{
+let element = __html_ui::HtmlElement { tag: __html_ui::HtmlTag::Named(::std::string::String::from("health-bar")), name_id: ::std::option::Option::None, classes: ::std::vec![], gap: __html_ui::Val::Auto, autofocus: false, callback: ::std::option::Option::None, each: ::std::option::Option::None, attributes: ::std::vec![(::std::string::String::from("color"), ::std::string::String::from("#e04040")),], children: ::std::vec![] };
+let (e3, _) = __html_ui::begin_element(world, e2, &element);
+__html_ui::end_element(world, e3, &element);
+}
+}
+__html_ui::end_element(world, e2, &element);
+}
+}
+__html_ui::end_element(world, e1, &element);
+}
+})